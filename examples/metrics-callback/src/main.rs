@@ -2,7 +2,7 @@ use fregate::{
     axum::{routing::get, Router},
     bootstrap, tokio, AppConfig, Application,
 };
-use metrics::counter;
+use metrics::{counter, gauge};
 use std::alloc::{GlobalAlloc, Layout};
 use std::sync::atomic::{AtomicU64, Ordering};
 
@@ -23,6 +23,11 @@ async fn main() {
             counter!("allocations", ALLOC.load(Ordering::Relaxed));
             counter!("deallocations", DEALLOC.load(Ordering::Relaxed));
         })
+        .metrics_async_callback("pool", || async {
+            // e.g. query connection pool or channel stats here
+            gauge!("pool_connections", 10.0);
+            Ok::<_, std::io::Error>(())
+        })
         .serve()
         .await
         .unwrap();
@@ -1,18 +1,23 @@
 pub mod health;
 pub(crate) mod management;
+pub mod routes;
 
 #[cfg(feature = "tls")]
 pub(crate) mod tls;
 
 use crate::application::health::{AlwaysReadyAndAlive, Health};
+use crate::application::routes::RouteInfo;
 use crate::configuration::{AppConfig, Empty};
 use crate::error::Result;
-use crate::management::build_management_router;
+use crate::management::{build_management_router, management_route_infos, ManagementRoutes};
 use crate::middleware::trace_request;
+use crate::observability::AsyncMetricsCallback;
 use axum::middleware::from_fn;
-use axum::Router;
+use axum::routing::MethodRouter;
+use axum::{Extension, Router};
 use hyper::Server;
-use std::fmt::{Debug, Formatter};
+use std::fmt::{Debug, Display, Formatter};
+use std::future::Future;
 use std::net::SocketAddr;
 use std::sync::Arc;
 use tokio::signal;
@@ -23,7 +28,9 @@ pub struct Application<'a, H = AlwaysReadyAndAlive, T = Empty> {
     config: &'a AppConfig<T>,
     health_indicator: H,
     router: Option<Router>,
+    management_routes: ManagementRoutes,
     metrics_callback: Option<Arc<dyn Fn() + Send + Sync + 'static>>,
+    metrics_async_callbacks: Vec<AsyncMetricsCallback>,
     use_default_trace_layer: bool,
 }
 
@@ -33,13 +40,22 @@ impl<'a, H: Debug, T: Debug> Debug for Application<'a, H, T> {
             config,
             health_indicator,
             router,
+            management_routes,
             metrics_callback,
+            metrics_async_callbacks,
             use_default_trace_layer,
         } = self;
         f.debug_struct("Application")
             .field("config", config)
             .field("health_indicator", health_indicator)
             .field("router", router)
+            .field(
+                "management_routes",
+                &management_routes
+                    .iter()
+                    .map(|(path, _)| path)
+                    .collect::<Vec<_>>(),
+            )
             .field("use_default_trace_layer", use_default_trace_layer)
             .field(
                 "metrics_callback",
@@ -49,6 +65,7 @@ impl<'a, H: Debug, T: Debug> Debug for Application<'a, H, T> {
                     &"None"
                 },
             )
+            .field("metrics_async_callbacks", metrics_async_callbacks)
             .finish()
     }
 }
@@ -60,7 +77,9 @@ impl<'a, T> Application<'a, T> {
             config,
             health_indicator: AlwaysReadyAndAlive::default(),
             router: None,
+            management_routes: Vec::new(),
             metrics_callback: None,
+            metrics_async_callbacks: Vec::new(),
             use_default_trace_layer: true,
         }
     }
@@ -73,7 +92,9 @@ impl<'a, H, T> Application<'a, H, T> {
             config,
             health_indicator: _,
             router,
+            management_routes,
             metrics_callback,
+            metrics_async_callbacks,
             use_default_trace_layer,
         } = self;
 
@@ -81,12 +102,15 @@ impl<'a, H, T> Application<'a, H, T> {
             config,
             health_indicator: health,
             router,
+            management_routes,
             metrics_callback,
+            metrics_async_callbacks,
             use_default_trace_layer,
         }
     }
 
-    /// Set up Router Application will serve to
+    /// Set up Router Application will serve to.\
+    /// Its routes are listed by `routes` management endpoint and on startup.
     #[must_use]
     pub fn router(self, router: Router) -> Self {
         Self {
@@ -95,6 +119,29 @@ impl<'a, H, T> Application<'a, H, T> {
         }
     }
 
+    /// Add custom route to management endpoints.\
+    /// Route is served next to health, metrics and version endpoints: it is nested under `management.prefix`,
+    /// protected with `management.auth` and is not traced by [`trace_request`].
+    /// ```no_run
+    /// use fregate::axum::routing::post;
+    /// use fregate::{AppConfig, Application};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     Application::new(&AppConfig::default())
+    ///         .management_route("/cache/flush", post(|| async { "flushed" }))
+    ///         .serve()
+    ///         .await
+    ///         .unwrap();
+    /// }
+    /// ```
+    #[must_use]
+    pub fn management_route(mut self, path: &str, method_router: MethodRouter) -> Self {
+        self.management_routes
+            .push((path.to_owned(), method_router));
+        self
+    }
+
     /// Set up callback which will be called before metrics will render.
     #[must_use]
     pub fn metrics_callback(self, metrics_callback: impl Fn() + Send + Sync + 'static) -> Self {
@@ -104,6 +151,36 @@ impl<'a, H, T> Application<'a, H, T> {
         }
     }
 
+    /// Add named async callback which will be called before metrics will render.\
+    /// All async callbacks run concurrently and are bounded by `server.metrics.callback.timeout`,
+    /// callback which fails or times out is logged and counted in `metrics_callback_failures_total` counter.
+    /// ```no_run
+    /// use fregate::{AppConfig, Application};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     Application::new(&AppConfig::default())
+    ///         .metrics_async_callback("pool", || async {
+    ///             // query connection pool stats and update gauges here
+    ///             Ok::<_, std::io::Error>(())
+    ///         })
+    ///         .serve()
+    ///         .await
+    ///         .unwrap();
+    /// }
+    /// ```
+    #[must_use]
+    pub fn metrics_async_callback<F, Fut, E>(mut self, name: &str, callback: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = std::result::Result<(), E>> + Send + 'static,
+        E: Display,
+    {
+        self.metrics_async_callbacks
+            .push(AsyncMetricsCallback::new(name, callback));
+        self
+    }
+
     /// Example:
     /// In this case [`trace_request`] is not attached to Application so no default tracing/metrics/logging for incoming requests
     /// ```no_run
@@ -187,6 +264,15 @@ impl<'a, H, T> Application<'a, H, T> {
     where
         H: Health,
     {
+        let mut routes = management_route_infos(
+            &self.config.management_cfg,
+            &self.config.observability_cfg,
+            &self.management_routes,
+        );
+        if let Some(router) = &self.router {
+            routes.extend(RouteInfo::from_router(router));
+        }
+
         let app_router = self
             .router
             .map(|router| {
@@ -204,13 +290,28 @@ impl<'a, H, T> Application<'a, H, T> {
             })
             .unwrap_or_default();
 
-        let router = build_management_router(
+        let management_router = build_management_router(
             &self.config.management_cfg,
             &self.config.observability_cfg,
             self.health_indicator,
             self.metrics_callback,
-        )
-        .merge(app_router);
+            self.metrics_async_callbacks,
+            self.management_routes,
+        );
+
+        info!(
+            target: "server",
+            "Routes: [{}]",
+            routes
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+
+        let router = management_router
+            .merge(app_router)
+            .layer(Extension(Arc::new(routes)));
 
         let application_socket = SocketAddr::new(self.config.host, self.config.port);
         (router, application_socket)
@@ -1,20 +1,110 @@
+mod auth;
+mod metrics;
+
 use crate::application::health::Health;
-use crate::observability::render_metrics;
-use crate::{ManagementConfig, ObservabilityConfig};
+use crate::application::routes::{method_router_methods, RouteInfo, RouteKind};
+use crate::observability::{run_metrics_callbacks, AsyncMetricsCallback};
+use crate::{Endpoint, ManagementAuth, ManagementConfig, ObservabilityConfig};
+use auth::authorize;
+use axum::http::HeaderMap;
+use axum::middleware::from_fn;
 use axum::response::IntoResponse;
-use axum::{routing::get, Extension, Router};
+use axum::routing::{MethodFilter, MethodRouter};
+use axum::{routing::get, Extension, Json, Router};
+use metrics::metrics_response;
 use std::sync::Arc;
+use tower_http::compression::CompressionLayer;
+
+/// Custom routes registered with [`crate::Application::management_route`].
+pub(crate) type ManagementRoutes = Vec<(String, MethodRouter)>;
 
 pub(crate) fn build_management_router<H: Health>(
     management_cfg: &ManagementConfig,
     observability_cfg: &ObservabilityConfig,
     health_indicator: H,
     callback: Option<Arc<dyn Fn() + Send + Sync + 'static>>,
+    async_callbacks: Vec<AsyncMetricsCallback>,
+    routes: ManagementRoutes,
 ) -> Router {
-    Router::new()
+    let router = Router::new()
         .merge(build_health_router(management_cfg, health_indicator))
-        .merge(build_metrics_router(management_cfg, callback))
+        .merge(build_metrics_router(
+            management_cfg,
+            observability_cfg,
+            callback,
+            async_callbacks,
+        ))
         .merge(build_version_router(management_cfg, observability_cfg))
+        .merge(build_routes_router(management_cfg))
+        .merge(build_custom_router(management_cfg, routes));
+
+    match prefix(management_cfg) {
+        Some(prefix) => Router::new().nest(prefix, router),
+        None => router,
+    }
+}
+
+/// Returns routes served by router built with [`build_management_router`].
+pub(crate) fn management_route_infos(
+    management_cfg: &ManagementConfig,
+    observability_cfg: &ObservabilityConfig,
+    routes: &ManagementRoutes,
+) -> Vec<RouteInfo> {
+    let endpoints = &management_cfg.endpoints;
+    let version = format!(
+        "/{}{}",
+        observability_cfg.component_name,
+        endpoints.version.as_ref()
+    );
+
+    let built_in = [
+        (&endpoints.health, endpoints.health.as_ref()),
+        (&endpoints.live, endpoints.live.as_ref()),
+        (&endpoints.ready, endpoints.ready.as_ref()),
+        (&endpoints.metrics, endpoints.metrics.as_ref()),
+        (&endpoints.version, version.as_str()),
+        (&endpoints.routes, endpoints.routes.as_ref()),
+    ]
+    .into_iter()
+    .filter(|(endpoint, _)| endpoint.is_enabled())
+    .map(|(_, path)| (path, MethodFilter::GET));
+    let custom = routes
+        .iter()
+        .map(|(path, method_router)| (path.as_str(), method_router_methods(method_router)));
+
+    let prefix = prefix(management_cfg).unwrap_or_default();
+    built_in
+        .chain(custom)
+        .flat_map(|(path, methods)| {
+            RouteInfo::with_methods(&format!("{prefix}{path}"), methods, RouteKind::Management)
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+fn prefix(management_cfg: &ManagementConfig) -> Option<&str> {
+    management_cfg
+        .prefix
+        .as_ref()
+        .map(|prefix| prefix.as_ref().trim_end_matches('/'))
+        .filter(|prefix| !prefix.is_empty())
+}
+
+fn route(router: Router, endpoint: &Endpoint, method_router: MethodRouter) -> Router {
+    if endpoint.is_enabled() {
+        router.route(endpoint.as_ref(), method_router)
+    } else {
+        router
+    }
+}
+
+fn with_auth(method_router: MethodRouter, auth: &ManagementAuth, exempt: bool) -> MethodRouter {
+    if exempt || !auth.is_enabled() {
+        return method_router;
+    }
+
+    let auth = Arc::new(auth.clone());
+    method_router.route_layer(from_fn(move |req, next| authorize(auth.clone(), req, next)))
 }
 
 fn build_health_router<H: Health>(
@@ -25,20 +115,52 @@ fn build_health_router<H: Health>(
     let alive_handler = |health: Extension<H>| async move { health.alive().await };
     let ready_handler = |health: Extension<H>| async move { health.ready().await };
 
-    Router::new()
-        .route(management_cfg.endpoints.health.as_ref(), get(alive_handler))
-        .route(management_cfg.endpoints.live.as_ref(), get(alive_handler))
-        .route(management_cfg.endpoints.ready.as_ref(), get(ready_handler))
-        .layer(Extension(health_indicator))
+    let endpoints = &management_cfg.endpoints;
+    let auth = &management_cfg.auth;
+    let exempt = auth.exempt.probes;
+
+    let router = route(
+        Router::new(),
+        &endpoints.health,
+        with_auth(get(alive_handler), auth, exempt),
+    );
+    let router = route(
+        router,
+        &endpoints.live,
+        with_auth(get(alive_handler), auth, exempt),
+    );
+    let router = route(
+        router,
+        &endpoints.ready,
+        with_auth(get(ready_handler), auth, exempt),
+    );
+
+    router.layer(Extension(health_indicator))
 }
 
 fn build_metrics_router(
     management_cfg: &ManagementConfig,
+    observability_cfg: &ObservabilityConfig,
     callback: Option<Arc<dyn Fn() + Send + Sync + 'static>>,
+    async_callbacks: Vec<AsyncMetricsCallback>,
 ) -> Router {
-    Router::new().route(
-        management_cfg.endpoints.metrics.as_ref(),
-        get(move || std::future::ready(render_metrics(callback.as_deref()))),
+    let auth = &management_cfg.auth;
+    let async_callbacks: Arc<[AsyncMetricsCallback]> = async_callbacks.into();
+    let timeout = observability_cfg.metrics_callback_timeout;
+
+    let handler = move |headers: HeaderMap| async move {
+        run_metrics_callbacks(&async_callbacks, timeout).await;
+        metrics_response(&headers, callback.as_deref())
+    };
+
+    route(
+        Router::new(),
+        &management_cfg.endpoints.metrics,
+        with_auth(
+            get(handler).layer(CompressionLayer::new()),
+            auth,
+            auth.exempt.metrics,
+        ),
     )
 }
 
@@ -46,21 +168,51 @@ fn build_version_router(
     management_cfg: &ManagementConfig,
     observability_cfg: &ObservabilityConfig,
 ) -> Router {
-    let path = format!(
-        "/{}{}",
-        observability_cfg.component_name,
-        management_cfg.endpoints.version.as_ref()
-    );
+    let endpoint = &management_cfg.endpoints.version;
+
+    if !endpoint.is_enabled() {
+        return Router::new();
+    }
+
+    let path = format!("/{}{}", observability_cfg.component_name, endpoint.as_ref());
     let version = observability_cfg.version.clone();
 
     Router::new().route(
         path.as_str(),
-        get(|| async move { version.into_response() }),
+        with_auth(
+            get(|| async move { version.into_response() }),
+            &management_cfg.auth,
+            false,
+        ),
     )
 }
 
+/// Served routes are provided with [`Extension`] once whole application router is built.
+fn build_routes_router(management_cfg: &ManagementConfig) -> Router {
+    let handler = |Extension(routes): Extension<Arc<Vec<RouteInfo>>>| async move {
+        Json(routes.as_ref().clone())
+    };
+
+    route(
+        Router::new(),
+        &management_cfg.endpoints.routes,
+        with_auth(get(handler), &management_cfg.auth, false),
+    )
+}
+
+fn build_custom_router(management_cfg: &ManagementConfig, routes: ManagementRoutes) -> Router {
+    routes
+        .into_iter()
+        .fold(Router::new(), |router, (path, method_router)| {
+            router.route(
+                path.as_str(),
+                with_auth(method_router, &management_cfg.auth, false),
+            )
+        })
+}
+
 #[cfg(test)]
-#[allow(clippy::unwrap_used)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
 mod management_test {
     use super::*;
     use crate::application::health::HealthResponse;
@@ -86,7 +238,8 @@ mod management_test {
         let mngmt_cfg = ManagementConfig::default();
         let obs_cfg = ObservabilityConfig::default();
 
-        let router = build_management_router(&mngmt_cfg, &obs_cfg, CustomHealth, None);
+        let router =
+            build_management_router(&mngmt_cfg, &obs_cfg, CustomHealth, None, vec![], vec![]);
         let request = Request::builder()
             .uri("http://0.0.0.0/health")
             .method("GET")
@@ -106,7 +259,8 @@ mod management_test {
         let mngmt_cfg = ManagementConfig::default();
         let obs_cfg = ObservabilityConfig::default();
 
-        let router = build_management_router(&mngmt_cfg, &obs_cfg, CustomHealth, None);
+        let router =
+            build_management_router(&mngmt_cfg, &obs_cfg, CustomHealth, None, vec![], vec![]);
         let request = Request::builder()
             .uri("http://0.0.0.0/live")
             .method("GET")
@@ -126,7 +280,8 @@ mod management_test {
         let mngmt_cfg = ManagementConfig::default();
         let obs_cfg = ObservabilityConfig::default();
 
-        let router = build_management_router(&mngmt_cfg, &obs_cfg, CustomHealth, None);
+        let router =
+            build_management_router(&mngmt_cfg, &obs_cfg, CustomHealth, None, vec![], vec![]);
         let request = Request::builder()
             .uri("http://0.0.0.0/ready")
             .method("GET")
@@ -148,7 +303,8 @@ mod management_test {
         let mut obs_cfg = ObservabilityConfig::default();
         obs_cfg.version = "123.220.0".to_owned();
 
-        let router = build_management_router(&mngmt_cfg, &obs_cfg, CustomHealth, None);
+        let router =
+            build_management_router(&mngmt_cfg, &obs_cfg, CustomHealth, None, vec![], vec![]);
         let request = Request::builder()
             .uri("http://0.0.0.0//version")
             .method("GET")
@@ -162,4 +318,259 @@ mod management_test {
         assert_eq!(StatusCode::OK, status);
         assert_eq!(&body[..], b"123.220.0");
     }
+
+    fn auth_cfg() -> ManagementConfig {
+        let mut mngmt_cfg = ManagementConfig::default();
+        mngmt_cfg.auth.bearer = Some("secret".to_owned());
+        mngmt_cfg.auth.allow = Some(vec!["127.0.0.0/8".parse().unwrap()]);
+        mngmt_cfg.auth.exempt.probes = true;
+        mngmt_cfg
+    }
+
+    fn request_from(uri: &str, remote: &str, token: Option<&str>) -> Request<hyper::Body> {
+        let mut request = Request::builder().uri(uri).method("GET");
+
+        if let Some(token) = token {
+            request = request.header("authorization", format!("Bearer {token}"));
+        }
+
+        let remote = remote.parse::<std::net::SocketAddr>().unwrap();
+        #[cfg(feature = "tls")]
+        let remote = crate::application::tls::RemoteAddr(remote);
+
+        let mut request = request.body(hyper::Body::empty()).unwrap();
+        request
+            .extensions_mut()
+            .insert(axum::extract::ConnectInfo(remote));
+        request
+    }
+
+    #[tokio::test]
+    async fn auth_test() {
+        let mngmt_cfg = auth_cfg();
+        let obs_cfg = ObservabilityConfig::default();
+        let router =
+            build_management_router(&mngmt_cfg, &obs_cfg, CustomHealth, None, vec![], vec![]);
+
+        for (token, remote, expected) in [
+            (None, "127.0.0.1:1000", StatusCode::UNAUTHORIZED),
+            (Some("wrong"), "127.0.0.1:1000", StatusCode::UNAUTHORIZED),
+            (Some("secret"), "10.0.0.1:1000", StatusCode::FORBIDDEN),
+            (Some("secret"), "127.0.0.1:1000", StatusCode::OK),
+        ] {
+            let request = request_from("http://0.0.0.0//version", remote, token);
+            let response = router.clone().oneshot(request).await.unwrap();
+
+            assert_eq!(expected, response.status());
+        }
+    }
+
+    #[tokio::test]
+    async fn auth_exempt_probes_test() {
+        let mngmt_cfg = auth_cfg();
+        let obs_cfg = ObservabilityConfig::default();
+        let router =
+            build_management_router(&mngmt_cfg, &obs_cfg, CustomHealth, None, vec![], vec![]);
+
+        let request = request_from("http://0.0.0.0/live", "10.0.0.1:1000", None);
+        let response = router.clone().oneshot(request).await.unwrap();
+        assert_eq!(StatusCode::OK, response.status());
+
+        let request = request_from("http://0.0.0.0/metrics", "10.0.0.1:1000", None);
+        let response = router.oneshot(request).await.unwrap();
+        assert_eq!(StatusCode::FORBIDDEN, response.status());
+    }
+
+    #[tokio::test]
+    async fn prefix_and_custom_route_test() {
+        let mut mngmt_cfg = auth_cfg();
+        mngmt_cfg.prefix = Some(Endpoint::new("/_/").unwrap());
+        mngmt_cfg.endpoints.ready = mngmt_cfg.endpoints.ready.with_enabled(false);
+        let obs_cfg = ObservabilityConfig::default();
+
+        let routes = vec![("/flush".to_owned(), get(|| async { "flushed" }))];
+        let router =
+            build_management_router(&mngmt_cfg, &obs_cfg, CustomHealth, None, vec![], routes);
+
+        for (uri, token, expected) in [
+            ("http://0.0.0.0/_/live", None, StatusCode::OK),
+            ("http://0.0.0.0/live", None, StatusCode::NOT_FOUND),
+            ("http://0.0.0.0/_/ready", None, StatusCode::NOT_FOUND),
+            ("http://0.0.0.0/_/flush", None, StatusCode::UNAUTHORIZED),
+            ("http://0.0.0.0/_/flush", Some("secret"), StatusCode::OK),
+            ("http://0.0.0.0/_//version", Some("secret"), StatusCode::OK),
+        ] {
+            let request = request_from(uri, "127.0.0.1:1000", token);
+            let response = router.clone().oneshot(request).await.unwrap();
+
+            assert_eq!(expected, response.status(), "{uri}");
+        }
+    }
+
+    #[tokio::test]
+    async fn metrics_content_negotiation_test() {
+        let mngmt_cfg = ManagementConfig::default();
+        let obs_cfg = ObservabilityConfig::default();
+        let router =
+            build_management_router(&mngmt_cfg, &obs_cfg, CustomHealth, None, vec![], vec![]);
+
+        for (accept, content_type) in [
+            (None, "text/plain; version=0.0.4; charset=utf-8"),
+            (
+                Some("application/openmetrics-text;version=1.0.0,text/plain;version=0.0.4;q=0.5"),
+                "application/openmetrics-text; version=1.0.0; charset=utf-8",
+            ),
+            (
+                Some("application/openmetrics-text;q=0.3,text/plain;q=0.5"),
+                "text/plain; version=0.0.4; charset=utf-8",
+            ),
+        ] {
+            let mut request = Request::builder()
+                .uri("http://0.0.0.0/metrics")
+                .method("GET");
+            if let Some(accept) = accept {
+                request = request.header("accept", accept);
+            }
+            let request = request.body(hyper::Body::empty()).unwrap();
+
+            let response = router.clone().oneshot(request).await.unwrap();
+
+            assert_eq!(StatusCode::OK, response.status());
+            assert_eq!(response.headers()["content-type"], content_type);
+        }
+    }
+
+    #[tokio::test]
+    async fn metrics_gzip_test() {
+        use ::metrics::{Key, Recorder};
+
+        crate::observability::recorder::get_recorder()
+            .register_counter(&Key::from_name("management_test_gzip_counter_total"))
+            .increment(1);
+
+        let mngmt_cfg = ManagementConfig::default();
+        let obs_cfg = ObservabilityConfig::default();
+        let router =
+            build_management_router(&mngmt_cfg, &obs_cfg, CustomHealth, None, vec![], vec![]);
+
+        let request = Request::builder()
+            .uri("http://0.0.0.0/metrics")
+            .method("GET")
+            .header("accept", "application/openmetrics-text")
+            .header("accept-encoding", "gzip")
+            .body(hyper::Body::empty())
+            .unwrap();
+
+        let response = router.oneshot(request).await.unwrap();
+
+        assert_eq!(StatusCode::OK, response.status());
+        assert_eq!(response.headers()["content-encoding"], "gzip");
+    }
+
+    #[tokio::test]
+    async fn metrics_async_callbacks_timeout_test() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::time::Duration;
+
+        let mngmt_cfg = ManagementConfig::default();
+        let obs_cfg = ObservabilityConfig {
+            metrics_callback_timeout: Some(Duration::from_millis(50)),
+            ..Default::default()
+        };
+
+        let called = Arc::new(AtomicBool::new(false));
+        let called_clone = called.clone();
+
+        let callbacks = vec![
+            AsyncMetricsCallback::new("slow", || async {
+                tokio::time::sleep(Duration::from_secs(60)).await;
+                Ok::<_, String>(())
+            }),
+            AsyncMetricsCallback::new("failing", || async { Err("failed") }),
+            AsyncMetricsCallback::new("ok", move || {
+                let called = called_clone.clone();
+                async move {
+                    called.store(true, Ordering::SeqCst);
+                    Ok::<_, String>(())
+                }
+            }),
+        ];
+
+        let router =
+            build_management_router(&mngmt_cfg, &obs_cfg, CustomHealth, None, callbacks, vec![]);
+        let request = Request::builder()
+            .uri("http://0.0.0.0/metrics")
+            .method("GET")
+            .body(hyper::Body::empty())
+            .unwrap();
+
+        let response = tokio::time::timeout(Duration::from_secs(5), router.oneshot(request))
+            .await
+            .expect("Metrics callback must not stall the scrape")
+            .unwrap();
+
+        assert_eq!(StatusCode::OK, response.status());
+        assert!(called.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn routes_disabled_by_default_test() {
+        let mngmt_cfg = ManagementConfig::default();
+        let obs_cfg = ObservabilityConfig::default();
+
+        let router =
+            build_management_router(&mngmt_cfg, &obs_cfg, CustomHealth, None, vec![], vec![]);
+        let routes = management_route_infos(&mngmt_cfg, &obs_cfg, &Vec::new());
+
+        let request = request_from("http://0.0.0.0/routes", "127.0.0.1:1000", None);
+        let response = router.oneshot(request).await.unwrap();
+
+        assert_eq!(StatusCode::NOT_FOUND, response.status());
+        assert!(!routes.iter().any(|route| route.path == "/routes"));
+    }
+
+    #[tokio::test]
+    async fn routes_test() {
+        let mut mngmt_cfg = ManagementConfig {
+            prefix: Some(Endpoint::new("/_/").unwrap()),
+            ..Default::default()
+        };
+        mngmt_cfg.endpoints.routes = mngmt_cfg.endpoints.routes.with_enabled(true);
+        mngmt_cfg.endpoints.ready = mngmt_cfg.endpoints.ready.with_enabled(false);
+        let obs_cfg = ObservabilityConfig::default();
+
+        let custom = vec![(
+            "/flush".to_owned(),
+            axum::routing::on(MethodFilter::POST | MethodFilter::PUT, || async {
+                "flushed"
+            }),
+        )];
+        let routes = management_route_infos(&mngmt_cfg, &obs_cfg, &custom);
+        let router =
+            build_management_router(&mngmt_cfg, &obs_cfg, CustomHealth, None, vec![], custom)
+                .layer(Extension(Arc::new(routes)));
+
+        let request = request_from("http://0.0.0.0/_/routes", "127.0.0.1:1000", None);
+        let response = router.oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let routes: Vec<serde_json::Value> = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(StatusCode::OK, status);
+        assert_eq!(
+            routes,
+            [
+                ("/_/health", "GET"),
+                ("/_/live", "GET"),
+                ("/_/metrics", "GET"),
+                ("/_//version", "GET"),
+                ("/_/routes", "GET"),
+                ("/_/flush", "POST"),
+                ("/_/flush", "PUT"),
+            ]
+            .map(|(path, method)| {
+                serde_json::json!({"path": path, "method": method, "kind": "management"})
+            })
+        );
+    }
 }
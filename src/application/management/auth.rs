@@ -0,0 +1,116 @@
+use crate::middleware::extract_remote_address;
+use crate::ManagementAuth;
+use axum::headers::authorization::{Basic, Bearer};
+use axum::headers::{Authorization, HeaderMapExt};
+use axum::http::{header::WWW_AUTHENTICATE, HeaderMap, Request, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use std::fmt::{Display, Formatter};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tracing::warn;
+
+const LOG_TARGET: &str = "management";
+
+#[derive(Debug, Clone, Copy)]
+enum Rejection {
+    NotAllowed,
+    MissingCredentials,
+    InvalidCredentials,
+}
+
+impl Display for Rejection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rejection::NotAllowed => f.write_str("address is not in allow-list"),
+            Rejection::MissingCredentials => f.write_str("missing credentials"),
+            Rejection::InvalidCredentials => f.write_str("invalid credentials"),
+        }
+    }
+}
+
+/// Fn to be used with [`axum::middleware::from_fn`] on management routes.
+pub(crate) async fn authorize<B>(
+    auth: Arc<ManagementAuth>,
+    request: Request<B>,
+    next: Next<B>,
+) -> Response {
+    let remote_address = extract_remote_address(&request).copied();
+
+    match check(&auth, remote_address, request.headers()) {
+        Ok(()) => next.run(request).await,
+        Err(rejection) => {
+            let path = request.uri().path();
+            let remote = remote_address.map_or_else(|| "unknown".to_owned(), |a| a.to_string());
+            warn!(target: LOG_TARGET, "Rejected request to `{path}` from `{remote}`: {rejection}.");
+
+            rejection_response(&auth, rejection)
+        }
+    }
+}
+
+fn check(
+    auth: &ManagementAuth,
+    remote_address: Option<SocketAddr>,
+    headers: &HeaderMap,
+) -> Result<(), Rejection> {
+    if auth.allow.is_some() {
+        let allowed = remote_address.is_some_and(|address| auth.is_allowed(address.ip()));
+
+        if !allowed {
+            return Err(Rejection::NotAllowed);
+        }
+    }
+
+    if !auth.requires_credentials() {
+        return Ok(());
+    }
+
+    let bearer = headers.typed_get::<Authorization<Bearer>>();
+    let basic = headers.typed_get::<Authorization<Basic>>();
+
+    if bearer.is_none() && basic.is_none() {
+        return Err(Rejection::MissingCredentials);
+    }
+
+    let bearer_matches =
+        auth.bearer
+            .as_deref()
+            .zip(bearer)
+            .is_some_and(|(expected, Authorization(bearer))| {
+                constant_time_eq(expected.as_bytes(), bearer.token().as_bytes())
+            });
+    let basic_matches =
+        auth.basic
+            .as_ref()
+            .zip(basic)
+            .is_some_and(|(expected, Authorization(basic))| {
+                constant_time_eq(expected.username.as_bytes(), basic.username().as_bytes())
+                    & constant_time_eq(expected.password.as_bytes(), basic.password().as_bytes())
+            });
+
+    if bearer_matches || basic_matches {
+        Ok(())
+    } else {
+        Err(Rejection::InvalidCredentials)
+    }
+}
+
+fn rejection_response(auth: &ManagementAuth, rejection: Rejection) -> Response {
+    match rejection {
+        Rejection::NotAllowed => StatusCode::FORBIDDEN.into_response(),
+        Rejection::MissingCredentials | Rejection::InvalidCredentials => {
+            let challenge = if auth.basic.is_some() {
+                r#"Basic realm="management""#
+            } else {
+                r#"Bearer realm="management""#
+            };
+
+            (StatusCode::UNAUTHORIZED, [(WWW_AUTHENTICATE, challenge)]).into_response()
+        }
+    }
+}
+
+fn constant_time_eq(lhs: &[u8], rhs: &[u8]) -> bool {
+    lhs.len() == rhs.len() && lhs.iter().zip(rhs).fold(0, |acc, (l, r)| acc | (l ^ r)) == 0
+}
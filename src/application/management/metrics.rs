@@ -0,0 +1,56 @@
+use crate::observability::{render_metrics, render_openmetrics};
+use axum::http::header::{ACCEPT, CONTENT_TYPE};
+use axum::http::HeaderMap;
+use axum::response::{IntoResponse, Response};
+
+const OPENMETRICS_MEDIA_TYPE: &str = "application/openmetrics-text";
+const OPENMETRICS_CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Renders metrics in format requested by `Accept` header with matching `Content-Type`.
+pub(crate) fn metrics_response(
+    headers: &HeaderMap,
+    callback: Option<&(dyn Fn() + Send + Sync + 'static)>,
+) -> Response {
+    if prefers_openmetrics(headers) {
+        (
+            [(CONTENT_TYPE, OPENMETRICS_CONTENT_TYPE)],
+            render_openmetrics(callback),
+        )
+            .into_response()
+    } else {
+        (
+            [(CONTENT_TYPE, PROMETHEUS_CONTENT_TYPE)],
+            render_metrics(callback),
+        )
+            .into_response()
+    }
+}
+
+/// Returns [`true`] if OpenMetrics has the highest quality value among acceptable formats.
+fn prefers_openmetrics(headers: &HeaderMap) -> bool {
+    let mut openmetrics_quality = 0.0_f32;
+    let mut text_quality = 0.0_f32;
+
+    headers
+        .get_all(ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .for_each(|media_range| {
+            let mut params = media_range.split(';');
+            let media_type = params.next().unwrap_or_default().trim();
+            let quality = params
+                .filter_map(|param| param.trim().strip_prefix("q="))
+                .find_map(|quality| quality.parse::<f32>().ok())
+                .unwrap_or(1.0);
+
+            match media_type {
+                OPENMETRICS_MEDIA_TYPE => openmetrics_quality = openmetrics_quality.max(quality),
+                "text/plain" | "text/*" | "*/*" => text_quality = text_quality.max(quality),
+                _ => {}
+            }
+        });
+
+    openmetrics_quality > 0.0 && openmetrics_quality >= text_quality
+}
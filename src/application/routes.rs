@@ -0,0 +1,255 @@
+//! Routes served by [`crate::Application`]
+use axum::routing::{MethodFilter, MethodRouter};
+use axum::Router;
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::{Display, Formatter};
+use std::sync::{Mutex, PoisonError};
+
+const GRPC_METHOD: &str = "POST";
+const ANY_METHOD: &str = "*";
+const NEST_TAIL_PARAM: &str = "__private__axum_nest_tail_param";
+const NEST_TAIL_REPLACEMENT: &str = "rest";
+const METHODS: [(MethodFilter, &str, &str); 8] = [
+    (MethodFilter::GET, "GET", "get"),
+    (MethodFilter::HEAD, "HEAD", "head"),
+    (MethodFilter::POST, "POST", "post"),
+    (MethodFilter::PUT, "PUT", "put"),
+    (MethodFilter::PATCH, "PATCH", "patch"),
+    (MethodFilter::DELETE, "DELETE", "delete"),
+    (MethodFilter::OPTIONS, "OPTIONS", "options"),
+    (MethodFilter::TRACE, "TRACE", "trace"),
+];
+
+/// Paths of Tonic services converted with [`crate::extensions::RouterTonicExt::from_tonic_service`].
+static GRPC_PATHS: Mutex<BTreeSet<String>> = Mutex::new(BTreeSet::new());
+
+/// Kind of route.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RouteKind {
+    /// Route of [`crate::Application::router`]
+    Http,
+    /// Tonic service converted with [`crate::extensions::RouterTonicExt::from_tonic_service`]
+    Grpc,
+    /// Management endpoint
+    Management,
+}
+
+impl Display for RouteKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RouteKind::Http => f.write_str("http"),
+            RouteKind::Grpc => f.write_str("grpc"),
+            RouteKind::Management => f.write_str("management"),
+        }
+    }
+}
+
+/// Route served by [`crate::Application`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct RouteInfo {
+    /// route path
+    pub path: String,
+    /// HTTP method
+    pub method: String,
+    /// route kind
+    pub kind: RouteKind,
+}
+
+impl RouteInfo {
+    /// Returns one [`RouteInfo`] for each method of `methods`.
+    pub(crate) fn with_methods(
+        path: &str,
+        methods: MethodFilter,
+        kind: RouteKind,
+    ) -> impl Iterator<Item = RouteInfo> + '_ {
+        METHODS
+            .into_iter()
+            .filter(move |(filter, _, _)| methods.contains(*filter))
+            .map(move |(_, method, _)| RouteInfo {
+                path: path.to_owned(),
+                method: method.to_owned(),
+                kind,
+            })
+    }
+
+    /// Returns routes served by `router`.\
+    /// axum doesn't expose registered routes, so they are read from [`Router`] debug output.
+    /// Routes without known methods, e.g. added with `route_service` or `nest_service`, are listed with `*` method.
+    pub(crate) fn from_router(router: &Router) -> Vec<RouteInfo> {
+        let mut routes = parse_router(&format!("{router:?}"))
+            .into_iter()
+            .flat_map(|(path, methods)| match methods {
+                Some(methods) => RouteInfo::with_methods(&path, methods, RouteKind::Http).collect(),
+                None if is_grpc(&path) => vec![RouteInfo {
+                    path,
+                    method: GRPC_METHOD.to_owned(),
+                    kind: RouteKind::Grpc,
+                }],
+                None => vec![RouteInfo {
+                    path,
+                    method: ANY_METHOD.to_owned(),
+                    kind: RouteKind::Http,
+                }],
+            })
+            .collect::<Vec<_>>();
+
+        routes.sort();
+        routes
+    }
+}
+
+/// Records path of Tonic service so that it is listed as gRPC route.
+pub(crate) fn register_grpc_path(path: String) {
+    GRPC_PATHS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(path);
+}
+
+fn is_grpc(path: &str) -> bool {
+    GRPC_PATHS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .contains(path)
+}
+
+/// Parses `Router { path_router: PathRouter { routes: {RouteId(1): MethodRouter(MethodRouter { get: BoxedHandler, head: None, .. }), ..}, node: Node { paths: {RouteId(1): "/path", ..} } }, fallback_router: .. }`
+/// into paths with methods, [`None`] methods stand for routes served by any service.
+fn parse_router(debug: &str) -> Vec<(String, Option<MethodFilter>)> {
+    let path_router = debug
+        .split(", fallback_router: ")
+        .next()
+        .unwrap_or_default();
+    let Some((endpoints, paths)) = path_router.split_once(", node: Node { paths: {") else {
+        return Vec::new();
+    };
+
+    let endpoints = endpoints
+        .split("RouteId(")
+        .skip(1)
+        .filter_map(|entry| entry.split_once("): "))
+        .collect::<HashMap<_, _>>();
+
+    paths
+        .split("RouteId(")
+        .skip(1)
+        .filter_map(|entry| {
+            let (id, path) = entry.split_once("): ")?;
+            let path = parse_str_literal(path)?.replace(NEST_TAIL_PARAM, NEST_TAIL_REPLACEMENT);
+            let endpoint = endpoints.get(id)?;
+
+            let methods = endpoint
+                .starts_with("MethodRouter(")
+                .then(|| parse_methods(endpoint));
+
+            Some((path, methods))
+        })
+        .collect()
+}
+
+/// Returns methods served by `method_router`, read from its debug output like [`RouteInfo::from_router`] does.
+pub(crate) fn method_router_methods(method_router: &MethodRouter) -> MethodFilter {
+    parse_methods(&format!("{method_router:?}"))
+}
+
+/// Parses `MethodRouter { get: BoxedHandler, head: None, .. }` into methods which are not `None`.
+fn parse_methods(debug: &str) -> MethodFilter {
+    METHODS
+        .into_iter()
+        .filter(|(_, _, field)| {
+            debug.contains(&format!(" {field}: ")) && !debug.contains(&format!(" {field}: None"))
+        })
+        .fold(MethodFilter::empty(), |methods, (filter, _, _)| {
+            methods | filter
+        })
+}
+
+/// Reads string written with [`Debug`] until its closing quote.
+fn parse_str_literal(str: &str) -> Option<String> {
+    let mut chars = str.strip_prefix('"')?.chars();
+    let mut result = String::new();
+
+    while let Some(char) = chars.next() {
+        match char {
+            '"' => return Some(result),
+            '\\' => result.push(chars.next()?),
+            char => result.push(char),
+        }
+    }
+
+    None
+}
+
+impl Display for RouteInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} ({})", self.method, self.path, self.kind)
+    }
+}
+
+#[cfg(test)]
+mod routes_test {
+    use super::*;
+
+    #[test]
+    fn with_methods_test() {
+        let routes = RouteInfo::with_methods(
+            "/items",
+            MethodFilter::POST | MethodFilter::PUT,
+            RouteKind::Http,
+        )
+        .map(|route| route.to_string())
+        .collect::<Vec<_>>();
+
+        assert_eq!(routes, ["POST /items (http)", "PUT /items (http)"]);
+    }
+
+    #[test]
+    fn from_router_test() {
+        use axum::routing::{get, post};
+
+        register_grpc_path("/grpc.Service/*rest".to_owned());
+
+        let router = Router::new()
+            .route("/items", get(|| async {}).post(|| async {}))
+            .route("/items/:id", post(|| async {}))
+            .nest("/api", Router::new().route("/v1", get(|| async {})))
+            .route_service(
+                "/grpc.Service/*rest",
+                tower::service_fn(|_| async {
+                    Ok::<_, std::convert::Infallible>(axum::response::Response::new(
+                        axum::body::Body::empty(),
+                    ))
+                }),
+            )
+            .nest_service(
+                "/static",
+                tower::service_fn(|_| async {
+                    Ok::<_, std::convert::Infallible>(axum::response::Response::new(
+                        axum::body::Body::empty(),
+                    ))
+                }),
+            )
+            .fallback(|| async {});
+
+        let routes = RouteInfo::from_router(&router)
+            .into_iter()
+            .map(|route| route.to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            routes,
+            [
+                "GET /api/v1 (http)",
+                "POST /grpc.Service/*rest (grpc)",
+                "GET /items (http)",
+                "POST /items (http)",
+                "POST /items/:id (http)",
+                "* /static (http)",
+                "* /static/ (http)",
+                "* /static/*rest (http)",
+            ]
+        );
+    }
+}
@@ -0,0 +1,59 @@
+//! Encrypts configuration values for [`fregate::AppConfigBuilder::build`].
+//!
+//! ```text
+//! fregate-encrypt --generate-key
+//! APP_CONFIG_KEY=<key> fregate-encrypt <value>
+//! echo -n <value> | APP_CONFIG_KEY_FILE=<path> fregate-encrypt
+//! ```
+use fregate::error::{Error, Result};
+use fregate::{
+    encrypt_value, generate_encryption_key, ENCRYPTION_KEY_ENV, ENCRYPTION_KEY_FILE_ENV,
+};
+use std::io::Read;
+use std::process::ExitCode;
+
+const GENERATE_KEY_ARG: &str = "--generate-key";
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(output) => {
+            println!("{output}");
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("fregate-encrypt: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run() -> Result<String> {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+
+    if args.iter().any(|arg| arg == GENERATE_KEY_ARG) {
+        return generate_encryption_key();
+    }
+
+    let key = read_key()?;
+    let value = if args.is_empty() {
+        let mut value = String::new();
+        std::io::stdin().read_to_string(&mut value)?;
+        value
+    } else {
+        args.join(" ")
+    };
+
+    encrypt_value(&key, &value)
+}
+
+fn read_key() -> Result<String> {
+    if let Ok(key) = std::env::var(ENCRYPTION_KEY_ENV) {
+        return Ok(key);
+    }
+
+    let path = std::env::var(ENCRYPTION_KEY_FILE_ENV).map_err(|_| {
+        Error::EncryptionError(format!("set `{ENCRYPTION_KEY_ENV}` or `{ENCRYPTION_KEY_FILE_ENV}`, or run with `{GENERATE_KEY_ARG}`"))
+    })?;
+
+    Ok(std::fs::read_to_string(path)?)
+}
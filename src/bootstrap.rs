@@ -1,13 +1,13 @@
 //!This is a shortcut fn to read [`AppConfig`] and call [`init_tracing`] and [`init_metrics`] fn.
 #[cfg(feature = "tokio-metrics")]
 use crate::observability::tokio_metrics::init_tokio_metrics_task;
-use crate::observability::{init_metrics, init_tracing};
+use crate::observability::{init_metrics_with_exemplars, init_tracing_with_config};
 use crate::{error::Result, *};
 use serde::de::DeserializeOwned;
 use std::fmt::Debug;
 
-/// Reads AppConfig and calls [`init_tracing`].
-/// Return Error if fails to read [`AppConfig`] or [`init_tracing`] returns error.
+/// Reads AppConfig and calls [`init_tracing_with_config`].
+/// Return Error if fails to read [`AppConfig`] or [`init_tracing_with_config`] returns error.
 /// Return Error if called twice because of internal call to [`tracing_subscriber::registry().try_init()`].
 ///```no_run
 /// use fregate::*;
@@ -36,39 +36,35 @@ where
     S: IntoIterator<Item = ConfigSource<'a>>,
     ConfigExt: Debug + DeserializeOwned,
 {
-    let mut config = AppConfig::<ConfigExt>::load_from(sources)?;
+    init(AppConfig::<ConfigExt>::load_from(sources)?)
+}
 
-    let ObservabilityConfig {
-        log_level,
-        version,
-        trace_level,
-        service_name,
-        component_name,
-        traces_endpoint,
-        msg_length,
-        buffered_lines_limit,
-        headers_filter,
-        ..
-    } = &config.observability_cfg;
+/// Same as [`bootstrap`] and validates [`AppConfig::private`] with its [`Validate`] implementation, see [`AppConfig::load_validated_from`].
+pub fn bootstrap_validated<'a, ConfigExt, S>(sources: S) -> Result<AppConfig<ConfigExt>>
+where
+    S: IntoIterator<Item = ConfigSource<'a>>,
+    ConfigExt: Debug + DeserializeOwned + Validate,
+{
+    init(AppConfig::<ConfigExt>::load_validated_from(sources)?)
+}
 
-    let worker_guard = init_tracing(
-        log_level,
-        trace_level,
-        version,
-        service_name,
-        component_name,
-        traces_endpoint.as_deref(),
-        *msg_length,
-        *buffered_lines_limit,
-        headers_filter.clone(),
-    )?;
+fn init<ConfigExt: Debug>(mut config: AppConfig<ConfigExt>) -> Result<AppConfig<ConfigExt>> {
+    let (worker_guard, log_guard) = init_tracing_with_config(&config.observability_cfg)?;
 
     config.worker_guard.replace(worker_guard);
-    init_metrics()?;
+    config.log_guard.replace(log_guard);
+    init_metrics_with_exemplars(config.observability_cfg.metrics_exemplars)?;
 
     #[cfg(feature = "tokio-metrics")]
     init_tokio_metrics_task(config.observability_cfg.metrics_update_interval);
 
+    tracing::info!("Active profiles: `{}`.", config.profiles.join(","));
     tracing::info!("Configuration: `{config:?}`.");
+    if let Some(provenance) = config.explain() {
+        tracing::info!("Configuration provenance: `{provenance}`.");
+    }
+    for unknown_key in &config.unknown_keys {
+        tracing::warn!("Unknown configuration key: `{unknown_key}`.");
+    }
     Ok(config)
 }
@@ -1,7 +1,22 @@
 //! See in [`examples`](https://github.com/elefant-dev/fregate-rs/blob/main/examples/configuration/src/main.rs) how to configure your [`crate::Application`]
 mod application;
+mod args;
+mod directory;
+mod dotenv;
+#[cfg(feature = "encryption")]
+mod encryption;
+mod interpolation;
+mod log_output;
+mod memory;
 mod observability;
+mod provenance;
+mod reload;
+mod schema;
+mod secrets;
 mod source;
+mod strict;
+mod units;
+mod validate;
 
 mod management;
 #[cfg(feature = "tls")]
@@ -9,9 +24,28 @@ mod tls;
 
 #[doc(inline)]
 pub use application::*;
+#[cfg(feature = "encryption")]
+#[doc(inline)]
+pub use encryption::*;
+#[doc(inline)]
+pub use log_output::*;
 #[doc(inline)]
 pub use management::*;
 #[doc(inline)]
 pub use observability::*;
 #[doc(inline)]
+pub use provenance::*;
+#[doc(inline)]
+pub use reload::*;
+#[doc(inline)]
+pub use schema::*;
+#[doc(inline)]
+pub use secrets::*;
+#[doc(inline)]
 pub use source::*;
+#[doc(inline)]
+pub use strict::*;
+#[doc(inline)]
+pub use units::*;
+#[doc(inline)]
+pub use validate::*;
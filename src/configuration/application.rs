@@ -1,32 +1,52 @@
+use crate::configuration::args::{args_help, is_help_requested, Args};
+use crate::configuration::directory::ConfDir;
+use crate::configuration::dotenv::DotEnv;
+use crate::configuration::interpolation::Interpolated;
+use crate::configuration::memory::{JsonValue, Values};
 use crate::configuration::observability::ObservabilityConfig;
+use crate::configuration::provenance::Provenance;
+use crate::configuration::secrets::{EnvFiles, EnvValues, Redacted, SecretsDir, SensitiveKeys};
 use crate::configuration::source::ConfigSource;
+use crate::configuration::strict::{unknown_keys, StrictMode};
+use crate::extensions::deserialize_lenient;
+use crate::observability::LogGuard;
 use crate::{error::Result, extensions::DeserializeExt, ManagementConfig};
-use config::{builder::DefaultState, ConfigBuilder, Environment, File, FileFormat};
+use crate::{ConfigSchema, Validate, ValidationError, ValidationErrors};
+use config::{Config, ConfigError, File, FileFormat, Map, Source};
 use serde::{
     de::{DeserializeOwned, Error},
     Deserialize, Deserializer,
 };
 use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
 use std::marker::PhantomData;
-use std::{fmt::Debug, net::IpAddr};
+use std::net::IpAddr;
 use tracing_appender::non_blocking::WorkerGuard;
 
+#[cfg(feature = "encryption")]
+use crate::configuration::encryption::{decrypt_config, DecryptionKey};
 #[cfg(feature = "tls")]
 use crate::configuration::tls::TlsConfigurationVariables;
 
 const HOST_PTR: &str = "/host";
 const PORT_PTR: &str = "/port";
 const MANAGEMENT_PTR: &str = "/management";
+const PROFILES_PTR: &str = "/profiles";
+const PROFILES_KEY: &str = "profiles";
+const PROFILES_ENV: &str = "APP_PROFILES";
+const PROVENANCE_KEY: &str = "config.provenance";
 
-const DEFAULT_CONFIG: &str = include_str!("../resources/default_conf.toml");
-const DEFAULT_SEPARATOR: &str = "_";
+pub(crate) const DEFAULT_CONFIG: &str = include_str!("../resources/default_conf.toml");
+pub(crate) const DEFAULT_SEPARATOR: &str = "_";
+/// Prefix of OpenTelemetry environment variables, which are always read.
+pub(crate) const OTEL_PREFIX: &str = "OTEL";
 
 /// Default private config for [`AppConfig`].
 #[derive(Deserialize, Debug, PartialEq, Eq, Copy, Clone)]
 pub struct Empty {}
 
 /// AppConfig reads and saves application configuration from different sources
-#[derive(Debug)]
 pub struct AppConfig<ConfigExt = Empty> {
     /// host address where to start Application
     pub host: IpAddr,
@@ -36,6 +56,16 @@ pub struct AppConfig<ConfigExt = Empty> {
     pub observability_cfg: ObservabilityConfig,
     /// configures management endpoints
     pub management_cfg: ManagementConfig,
+    /// active profiles, see [`ConfigSource::Profiled`]
+    pub profiles: Vec<String>,
+    /// sources of each key if recorded, see [`AppConfig::explain`]
+    pub provenance: Option<Provenance>,
+    /// keys not consumed by fregate or `ConfigExt`, see [`AppConfigBuilder::strict`]
+    pub unknown_keys: Vec<ValidationError>,
+    /// merged configuration tree, see [`AppConfig::section`] and [`AppConfig::get`]
+    pub tree: Value,
+    /// keys which values were read from secret files and are hidden in [`AppConfig::tree`] and debug output
+    pub sensitive_keys: SensitiveKeys,
     /// TLS configuration parameters
     #[cfg(feature = "tls")]
     pub tls: TlsConfigurationVariables,
@@ -44,6 +74,48 @@ pub struct AppConfig<ConfigExt = Empty> {
     /// Why it is here read more: [`https://docs.rs/tracing-appender/latest/tracing_appender/non_blocking/struct.WorkerGuard.html`]
     /// This one will not be cloned and will be set to [`None`] in clone.
     pub worker_guard: Option<WorkerGuard>,
+    /// Flushes logs of [`ObservabilityConfig::log_sinks`] on drop, see [`LogGuard`].
+    /// This one will not be cloned and will be set to [`None`] in clone.
+    pub log_guard: Option<LogGuard>,
+}
+
+impl<ConfigExt: Debug> Debug for AppConfig<ConfigExt> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let Self {
+            host,
+            port,
+            observability_cfg,
+            management_cfg,
+            profiles,
+            provenance: _,
+            unknown_keys: _,
+            tree: _,
+            sensitive_keys,
+            #[cfg(feature = "tls")]
+            tls,
+            private,
+            worker_guard,
+            log_guard,
+        } = self;
+
+        let mut debug = f.debug_struct("AppConfig");
+        debug
+            .field("host", host)
+            .field("port", port)
+            .field(
+                "observability_cfg",
+                &Redacted(observability_cfg, sensitive_keys),
+            )
+            .field("management_cfg", &Redacted(management_cfg, sensitive_keys))
+            .field("profiles", &Redacted(profiles, sensitive_keys));
+        #[cfg(feature = "tls")]
+        debug.field("tls", &Redacted(tls, sensitive_keys));
+        debug
+            .field("private", &Redacted(private, sensitive_keys))
+            .field("worker_guard", worker_guard)
+            .field("log_guard", log_guard)
+            .finish()
+    }
 }
 
 impl<ConfigExt> Clone for AppConfig<ConfigExt>
@@ -56,10 +128,16 @@ where
             port: self.port,
             observability_cfg: self.observability_cfg.clone(),
             management_cfg: self.management_cfg.clone(),
+            profiles: self.profiles.clone(),
+            provenance: self.provenance.clone(),
+            unknown_keys: self.unknown_keys.clone(),
+            tree: self.tree.clone(),
+            sensitive_keys: self.sensitive_keys.clone(),
             #[cfg(feature = "tls")]
             tls: self.tls.clone(),
             private: self.private.clone(),
             worker_guard: None,
+            log_guard: None,
         }
     }
 }
@@ -77,32 +155,65 @@ where
         let host = config.pointer_and_deserialize(HOST_PTR)?;
         let port = config.pointer_and_deserialize(PORT_PTR)?;
         let management_cfg = config
-            .pointer_and_deserialize::<_, D::Error>(MANAGEMENT_PTR)
+            .pointer(MANAGEMENT_PTR)
+            .map(ManagementConfig::deserialize)
+            .transpose()
+            .map_err(Error::custom)?
+            .unwrap_or_default();
+        let profiles = config
+            .pointer(PROFILES_PTR)
+            .map(parse_profiles)
             .unwrap_or_default();
         let observability_cfg = ObservabilityConfig::deserialize(&config).map_err(Error::custom)?;
         #[cfg(feature = "tls")]
         let tls = TlsConfigurationVariables::deserialize(&config).map_err(Error::custom)?;
-        let private = ConfigExt::deserialize(config).map_err(Error::custom)?;
+        let private = ConfigExt::deserialize(&config)
+            .or_else(|err| deserialize_lenient(&config).ok_or(err))
+            .map_err(Error::custom)?;
 
         Ok(AppConfig::<ConfigExt> {
             host,
             port,
             observability_cfg,
             management_cfg,
+            profiles,
+            provenance: None,
+            unknown_keys: Vec::new(),
+            tree: config,
+            sensitive_keys: SensitiveKeys::default(),
             #[cfg(feature = "tls")]
             tls,
             private,
             worker_guard: None,
+            log_guard: None,
         })
     }
 }
 
+/// Reads profiles set either as comma-separated string or as a list.
+fn parse_profiles(value: &Value) -> Vec<String> {
+    match value {
+        Value::String(profiles) => profiles
+            .split(',')
+            .map(str::trim)
+            .filter(|profile| !profile.is_empty())
+            .map(ToOwned::to_owned)
+            .collect(),
+        Value::Array(profiles) => profiles
+            .iter()
+            .filter_map(Value::as_str)
+            .map(ToOwned::to_owned)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
 impl Default for AppConfig {
     #[allow(clippy::expect_used)]
     fn default() -> Self {
         AppConfig::builder()
             .add_default()
-            .add_env_prefixed("OTEL")
+            .add_env_prefixed(OTEL_PREFIX)
             .build()
             .expect("Default config never fails")
     }
@@ -123,38 +234,171 @@ impl<ConfigExt> AppConfig<ConfigExt> {
     {
         AppConfig::builder()
             .add_default()
-            .add_env_prefixed("OTEL")
+            .add_env_prefixed(OTEL_PREFIX)
             .add_file(file_path)
             .add_env_prefixed(env_prefix)
             .build()
     }
 
-    /// Load configuration from provided container with [`ConfigSource`] which override default config.
+    /// Load configuration from provided container with [`ConfigSource`] which override default config.\
+    /// Only fregate sections are validated, see [`AppConfig::load_validated_from`] to validate [`AppConfig::private`] as well.
     pub fn load_from<'a, S>(sources: S) -> Result<Self>
     where
         ConfigExt: Debug + DeserializeOwned,
         S: IntoIterator<Item = ConfigSource<'a>>,
     {
+        Self::sources_builder(sources)?.build()
+    }
+
+    /// Same as [`AppConfig::load_from`] and validates [`AppConfig::private`] with its [`Validate`] implementation.
+    /// ```no_run
+    /// use fregate::{AppConfig, ConfigSource, Validate, ValidationErrors};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Debug, Deserialize)]
+    /// struct Private {
+    ///     workers: usize,
+    /// }
+    ///
+    /// impl Validate for Private {
+    ///     fn validate(&self, errors: &mut ValidationErrors) {
+    ///         if self.workers == 0 {
+    ///             errors.add("/workers", "must be greater than 0");
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let config = AppConfig::<Private>::load_validated_from([ConfigSource::EnvPrefix("TEST")]);
+    /// ```
+    pub fn load_validated_from<'a, S>(sources: S) -> Result<Self>
+    where
+        ConfigExt: Debug + DeserializeOwned + Validate,
+        S: IntoIterator<Item = ConfigSource<'a>>,
+    {
+        Self::sources_builder(sources)?.validate_private().build()
+    }
+
+    /// Builder with default configuration, `OTEL` environment variables and given sources.\
+    /// Returns [`crate::error::Error::HelpRequested`] if [`ConfigSource::Args`] contain `--help` or `-h`.
+    pub(crate) fn sources_builder<'a, S>(sources: S) -> Result<AppConfigBuilder<ConfigExt>>
+    where
+        S: IntoIterator<Item = ConfigSource<'a>>,
+    {
+        let sources = sources.into_iter().collect::<Vec<_>>();
+        let env_prefixes = std::iter::once(OTEL_PREFIX)
+            .chain(sources.iter().filter_map(|source| match source {
+                ConfigSource::EnvPrefix(prefix) => Some(*prefix),
+                _ => None,
+            }))
+            .collect::<Vec<_>>();
+
         let mut config_builder = AppConfig::<ConfigExt>::builder()
             .add_default()
-            .add_env_prefixed("OTEL");
+            .add_env_prefixed(OTEL_PREFIX);
 
         for source in sources {
             config_builder = match source {
                 ConfigSource::String(str, format) => config_builder.add_str(str, format),
                 ConfigSource::File(path) => config_builder.add_file(path),
+                ConfigSource::Profiled(path) => config_builder.add_profiled(path),
                 ConfigSource::EnvPrefix(prefix) => config_builder.add_env_prefixed(prefix),
+                ConfigSource::SecretsDir(path) => config_builder.add_secrets_dir(path),
+                ConfigSource::Value(value) => config_builder.add_value(value),
+                ConfigSource::Map(values) => config_builder.add_map(values),
+                ConfigSource::Dir(path) => config_builder.add_dir(path),
+                ConfigSource::DotEnv(path) => {
+                    env_prefixes.iter().fold(config_builder, |builder, prefix| {
+                        builder.add_dotenv(path, prefix)
+                    })
+                }
+                ConfigSource::Args => {
+                    let args = std::env::args().skip(1).collect::<Vec<_>>();
+                    if is_help_requested(&args) {
+                        return Err(crate::error::Error::HelpRequested(Self::args_help()));
+                    }
+
+                    config_builder.add_args(args)
+                }
             };
         }
 
-        config_builder.build()
+        Ok(config_builder)
+    }
+
+    /// Returns which source supplied each resolved key and which sources it overrode.\
+    /// Recorded only if `config.provenance` is set to `true` or [`AppConfigBuilder::with_provenance`] is called.
+    /// ```no_run
+    /// use fregate::{AppConfig, ConfigSource, Empty};
+    ///
+    /// std::env::set_var("TEST_CONFIG_PROVENANCE", "true");
+    ///
+    /// let config = AppConfig::<Empty>::load_from([ConfigSource::EnvPrefix("TEST")]).unwrap();
+    ///
+    /// if let Some(provenance) = config.explain() {
+    ///     println!("{provenance}");
+    /// }
+    /// ```
+    pub fn explain(&self) -> Option<&Provenance> {
+        self.provenance.as_ref()
+    }
+
+    /// Deserializes section at JSON pointer, so libraries might read their own sections without `ConfigExt`.
+    /// ```
+    /// use fregate::{serde_json::json, AppConfig, ConfigSource, Empty};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Database {
+    ///     url: String,
+    /// }
+    ///
+    /// let config = AppConfig::<Empty>::load_from([ConfigSource::Value(json!({
+    ///     "database": { "url": "postgres://localhost" }
+    /// }))])?;
+    ///
+    /// let database = config.section::<Database>("/database")?;
+    /// assert_eq!(database.url, "postgres://localhost");
+    /// # Ok::<(), fregate::error::Error>(())
+    /// ```
+    pub fn section<T: DeserializeOwned>(&self, pointer: &str) -> Result<T> {
+        let value =
+            self.tree
+                .pointer(pointer)
+                .ok_or_else(|| crate::error::Error::ConfigSectionError {
+                    pointer: pointer.to_owned(),
+                    message: "missing key".to_owned(),
+                })?;
+
+        T::deserialize(value).map_err(|err| crate::error::Error::ConfigSectionError {
+            pointer: pointer.to_owned(),
+            message: err.to_string(),
+        })
+    }
+
+    /// Deserializes value at key with nested keys separated with `.`, e.g. `db.pool.size`, see [`AppConfig::section`].
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Result<T> {
+        self.section(&key_pointer(key))
+    }
+
+    /// Returns help for [`ConfigSource::Args`] listing known fregate keys with their defaults.
+    pub fn args_help() -> String {
+        args_help(DEFAULT_CONFIG)
     }
 }
 
 /// AppConfig builder to set up multiple sources
 #[derive(Debug, Default)]
 pub struct AppConfigBuilder<ConfigExt> {
-    builder: ConfigBuilder<DefaultState>,
+    sources: Vec<(String, Box<dyn Source + Send + Sync>)>,
+    overrides: Vec<(String, Box<dyn Source + Send + Sync>)>,
+    args: Vec<(String, Box<dyn Source + Send + Sync>)>,
+    secret_sources: Vec<String>,
+    error: Option<crate::error::Error>,
+    provenance: bool,
+    strict: Option<(StrictMode, fn() -> Value)>,
+    #[cfg(feature = "encryption")]
+    decryption_key: Option<DecryptionKey>,
+    pub(crate) validate_private: Option<fn(&ConfigExt, &mut ValidationErrors)>,
     phantom: PhantomData<ConfigExt>,
 }
 
@@ -162,53 +406,336 @@ impl<ConfigExt> AppConfigBuilder<ConfigExt> {
     /// Creates new [`AppConfigBuilder`]
     pub fn new() -> Self {
         Self {
-            builder: ConfigBuilder::default(),
+            sources: Vec::new(),
+            overrides: Vec::new(),
+            args: Vec::new(),
+            secret_sources: Vec::new(),
+            error: None,
+            provenance: false,
+            strict: None,
+            #[cfg(feature = "encryption")]
+            decryption_key: None,
+            validate_private: None,
             phantom: PhantomData,
         }
     }
 
-    /// Reads all registered sources
-    pub fn build(self) -> Result<AppConfig<ConfigExt>>
+    /// Reads all registered sources and validates result with [`Validate`].\
+    /// Returns [`crate::error::Error::ValidationError`] listing every invalid value with its JSON pointer and source.
+    pub fn build(mut self) -> Result<AppConfig<ConfigExt>>
     where
         ConfigExt: Debug + DeserializeOwned,
     {
-        Ok(self
-            .builder
-            .build()?
-            .try_deserialize::<AppConfig<ConfigExt>>()?)
+        if let Some(err) = self.error.take() {
+            return Err(err);
+        }
+
+        let collected = self.collect()?;
+        let config = merge(&collected)?;
+        #[cfg(feature = "encryption")]
+        let (config, decrypted) = decrypt_config(config, self.decryption_key.as_ref())?;
+        #[cfg(not(feature = "encryption"))]
+        let decrypted = Vec::<String>::new();
+        let provenance = Provenance::collect(&collected);
+        let record_provenance =
+            self.provenance || config.get_bool(PROVENANCE_KEY).unwrap_or_default();
+        let tree = match self.strict {
+            Some(_) => Some(config.clone().try_deserialize::<Value>()?),
+            None => None,
+        };
+        let mut app_config = config.try_deserialize::<AppConfig<ConfigExt>>()?;
+        app_config.sensitive_keys = SensitiveKeys::redact(
+            provenance
+                .keys_set_by(&self.secret_sources)
+                .chain(decrypted.iter().map(String::as_str)),
+            &mut app_config.tree,
+        );
+
+        if record_provenance {
+            app_config.provenance = Some(provenance.clone());
+        }
+
+        let mut errors = ValidationErrors::default();
+        app_config.validate(&mut errors);
+        if let Some(validate_private) = self.validate_private {
+            validate_private(&app_config.private, &mut errors);
+        }
+
+        if let (Some((mode, schema)), Some(tree)) = (self.strict, tree) {
+            match mode {
+                StrictMode::Deny => unknown_keys(&tree, &schema(), &mut errors),
+                StrictMode::Warn => {
+                    let mut unknown = ValidationErrors::default();
+                    unknown_keys(&tree, &schema(), &mut unknown);
+                    app_config.unknown_keys = unknown.with_sources(&provenance).errors().to_vec();
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(app_config)
+        } else {
+            Err(errors.with_sources(&provenance).into())
+        }
     }
 
-    /// Add default config
+    /// Reads each source once, in order they were added, followed by overrides and command-line arguments.
+    fn collect(&self) -> Result<Vec<(String, Map<String, config::Value>)>> {
+        self.sources
+            .iter()
+            .chain(&self.overrides)
+            .chain(&self.args)
+            .map(|(name, source)| Ok((name.clone(), source.collect()?)))
+            .collect()
+    }
+
+    /// Record sources of each key, see [`AppConfig::explain`].
     #[must_use]
-    pub fn add_default(mut self) -> Self {
-        self.builder = self
-            .builder
-            .add_source(File::from_str(DEFAULT_CONFIG, FileFormat::Toml));
+    pub fn with_provenance(self) -> Self {
+        Self {
+            provenance: true,
+            ..self
+        }
+    }
+
+    /// Read key for `ENC[AES256_GCM,...]` values from given source instead of
+    /// [`crate::ENCRYPTION_KEY_ENV`] or [`crate::ENCRYPTION_KEY_FILE_ENV`], see [`crate::encrypt_value`].
+    #[cfg(feature = "encryption")]
+    #[must_use]
+    pub fn decryption_key(self, key: DecryptionKey) -> Self {
+        Self {
+            decryption_key: Some(key),
+            ..self
+        }
+    }
+
+    /// Report keys which are not described by fregate and `ConfigExt` [`ConfigSchema`] on [`AppConfigBuilder::build`],
+    /// e.g. `TEST_MANAGMENT_ENDPOINTS_METRICS` or `log.levle`, with closest known key as suggestion.
+    /// ```no_run
+    /// use fregate::{AppConfig, Empty, StrictMode};
+    ///
+    /// let config = AppConfig::<Empty>::builder()
+    ///     .add_default()
+    ///     .add_env_prefixed("TEST")
+    ///     .strict(StrictMode::Deny)
+    ///     .build();
+    /// ```
+    #[must_use]
+    pub fn strict(mut self, mode: StrictMode) -> Self
+    where
+        ConfigExt: ConfigSchema,
+    {
+        self.strict = Some((mode, AppConfig::<ConfigExt>::json_schema));
         self
     }
 
-    /// Add file
+    /// Validate [`AppConfig::private`] with its [`Validate`] implementation on [`AppConfigBuilder::build`].
     #[must_use]
-    pub fn add_file(mut self, path: &str) -> Self {
-        self.builder = self.builder.add_source(File::with_name(path));
+    pub fn validate_private(mut self) -> Self
+    where
+        ConfigExt: Validate,
+    {
+        self.validate_private = Some(ConfigExt::validate);
         self
     }
 
-    /// Add string
+    /// Add default config
+    #[must_use]
+    pub fn add_default(self) -> Self {
+        self.add_source(
+            "default configuration".to_owned(),
+            File::from_str(DEFAULT_CONFIG, FileFormat::Toml),
+        )
+    }
+
+    /// Add file, `${VAR}` and `${VAR:-default}` in string values are replaced with environment variables, `$${` is read as literal `${`
+    #[must_use]
+    pub fn add_file(self, path: &str) -> Self {
+        let name = format!("file `{path}`");
+        self.add_source(name.clone(), Interpolated::new(name, File::with_name(path)))
+    }
+
+    /// Add every `toml`, `yaml` and `json` file in directory in lexical order, see [`ConfigSource::Dir`]
     #[must_use]
-    pub fn add_str(mut self, str: &str, format: FileFormat) -> Self {
-        self.builder = self.builder.add_source(File::from_str(str, format));
+    pub fn add_dir(self, path: &str) -> Self {
+        self.add_source(format!("directory `{path}`"), ConfDir::new(path))
+    }
+
+    /// Add base file and `{path}.{profile}` file for each active profile, see [`ConfigSource::Profiled`].\
+    /// Error of reading profiles from already added sources is returned by [`AppConfigBuilder::build`].
+    #[must_use]
+    pub fn add_profiled(self, path: &str) -> Self {
+        let mut builder = self.add_file(path);
+        let profiles = match builder.active_profiles() {
+            Ok(profiles) => profiles,
+            Err(err) => {
+                builder.error.get_or_insert(err);
+                return builder;
+            }
+        };
+
+        let builder = profiles.iter().fold(builder, |builder, profile| {
+            let profile_path = format!("{path}.{profile}");
+            let name = format!("file `{profile_path}`");
+            builder.add_source(
+                name.clone(),
+                Interpolated::new(name, File::with_name(&profile_path).required(false)),
+            )
+        });
+
+        builder.set_profiles(profiles)
+    }
+
+    /// Reads profiles from `APP_PROFILES` environment variable or `profiles` key of already added sources.
+    fn active_profiles(&self) -> Result<Vec<String>> {
+        if let Ok(profiles) = std::env::var(PROFILES_ENV) {
+            return Ok(parse_profiles(&Value::String(profiles)));
+        }
+
+        match merge(&self.collect()?)?.get::<Value>(PROFILES_KEY) {
+            Ok(profiles) => Ok(parse_profiles(&profiles)),
+            Err(ConfigError::NotFound(_)) => Ok(Vec::new()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn set_profiles(mut self, profiles: Vec<String>) -> Self {
+        self.overrides.push((
+            format!("override `{PROFILES_KEY}`"),
+            Box::new(Values::single(PROFILES_KEY, profiles.into())),
+        ));
         self
     }
 
-    /// Add environment variables with specified prefix and default separator: "_"
+    /// Add string, environment variables are interpolated the same way as in [`AppConfigBuilder::add_file`]
     #[must_use]
-    pub fn add_env_prefixed(mut self, prefix: &str) -> Self {
-        self.builder = self.builder.add_source(
-            Environment::with_prefix(prefix)
-                .try_parsing(true)
-                .separator(DEFAULT_SEPARATOR),
-        );
+    pub fn add_str(self, str: &str, format: FileFormat) -> Self {
+        let name = "string".to_owned();
+        self.add_source(
+            name.clone(),
+            Interpolated::new(name, File::from_str(str, format)),
+        )
+    }
+
+    /// Add top-level keys of JSON object, see [`ConfigSource::Value`]
+    #[must_use]
+    pub fn add_value(self, value: Value) -> Self {
+        self.add_source("value".to_owned(), JsonValue::new(value))
+    }
+
+    /// Add values by keys with nested keys separated with `.`, see [`ConfigSource::Map`]
+    #[must_use]
+    pub fn add_map(self, values: HashMap<String, String>) -> Self {
+        self.add_source("map".to_owned(), Values::from_strings(values))
+    }
+
+    /// Set value by key with nested keys separated with `.`, overrides have priority over all sources.\
+    /// Returns error if key is not a valid path.
+    /// ```
+    /// use fregate::{AppConfig, Empty};
+    ///
+    /// let config = AppConfig::<Empty>::builder()
+    ///     .add_default()
+    ///     .set_override("port", 9000)?
+    ///     .set_override("log.level", "debug")?
+    ///     .build()?;
+    ///
+    /// assert_eq!(config.port, 9000);
+    /// # Ok::<(), fregate::error::Error>(())
+    /// ```
+    pub fn set_override<T>(mut self, key: &str, value: T) -> Result<Self>
+    where
+        T: Into<config::Value>,
+    {
+        let value = value.into();
+
+        // validates key path
+        Config::builder().set_override(key, value.clone())?;
+        self.overrides.push((
+            format!("override `{key}`"),
+            Box::new(Values::single(key, value)),
+        ));
+        Ok(self)
+    }
+
+    /// Add environment variables with specified prefix and default separator: "_".\
+    /// `{PREFIX}_{KEY}_FILE` variable is read as path to file with value of `{KEY}`, see [`ConfigSource::EnvPrefix`].
+    #[must_use]
+    pub fn add_env_prefixed(self, prefix: &str) -> Self {
+        self.add_source(
+            format!("environment `{prefix}{DEFAULT_SEPARATOR}*`"),
+            EnvValues::new(prefix, DEFAULT_SEPARATOR),
+        )
+        .add_secret_source(
+            format!("environment `{prefix}{DEFAULT_SEPARATOR}*_FILE`"),
+            EnvFiles::new(prefix, DEFAULT_SEPARATOR),
+        )
+    }
+
+    /// Add variables with specified prefix from `.env` file, mapped the same way as [`AppConfigBuilder::add_env_prefixed`].\
+    /// Process environment is not changed, missing file is not an error.
+    #[must_use]
+    pub fn add_dotenv(self, path: &str, prefix: &str) -> Self {
+        self.add_source(
+            format!("dotenv `{path}` `{prefix}{DEFAULT_SEPARATOR}*`"),
+            DotEnv::new(path, prefix, DEFAULT_SEPARATOR),
+        )
+    }
+
+    /// Add command-line arguments, see [`ConfigSource::Args`].\
+    /// Arguments have priority over all sources and overrides regardless of order they were added in.
+    #[must_use]
+    pub fn add_args<I>(mut self, args: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.args.push((
+            "command-line arguments".to_owned(),
+            Box::new(Args::new(args.into_iter().map(Into::into).collect())),
+        ));
+        self
+    }
+
+    /// Add directory with secret files, see [`ConfigSource::SecretsDir`]
+    #[must_use]
+    pub fn add_secrets_dir(self, path: &str) -> Self {
+        self.add_secret_source(format!("secrets directory `{path}`"), SecretsDir::new(path))
+    }
+
+    /// Adds source which values are hidden, see [`SensitiveKeys`].
+    fn add_secret_source<T>(mut self, name: String, source: T) -> Self
+    where
+        T: Source + Send + Sync + 'static,
+    {
+        self.secret_sources.push(name.clone());
+        self.add_source(name, source)
+    }
+
+    fn add_source<T>(mut self, name: String, source: T) -> Self
+    where
+        T: Source + Send + Sync + 'static,
+    {
+        self.sources.push((name, Box::new(source)));
         self
     }
 }
+
+/// Returns JSON pointer of key with nested keys separated with `.`, e.g. `/db/pool/size` for `db.pool.size`.
+pub(crate) fn key_pointer(key: &str) -> String {
+    key.split('.')
+        .map(|segment| format!("/{}", segment.replace('~', "~0").replace('/', "~1")))
+        .collect()
+}
+
+/// Merges values read from sources, later ones override earlier ones.
+fn merge(collected: &[(String, Map<String, config::Value>)]) -> Result<Config> {
+    let config = collected
+        .iter()
+        .fold(Config::builder(), |builder, (_, values)| {
+            builder.add_source(Values::new(values.clone()))
+        })
+        .build()?;
+
+    Ok(config)
+}
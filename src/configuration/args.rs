@@ -0,0 +1,136 @@
+use crate::configuration::strict::is_known;
+use crate::{AppConfig, Empty};
+use config::{Config, ConfigError, File, FileFormat, Map, Source, Value, ValueKind};
+use std::fmt::Write;
+
+const ORIGIN: &str = "command-line arguments";
+const SET_ARG: &str = "--set";
+const HELP_ARGS: [&str; 2] = ["--help", "-h"];
+
+/// Returns [`true`] if `--help` or `-h` is given.
+pub(crate) fn is_help_requested(args: &[String]) -> bool {
+    args.iter().any(|arg| HELP_ARGS.contains(&arg.as_str()))
+}
+
+/// Returns help listing keys with defaults from given TOML config, e.g. `default_conf.toml`.
+pub(crate) fn args_help(default_config: &str) -> String {
+    let mut help = String::from(
+        "Options:\n  --<key> <value>, --<key>=<value>, --set <key>=<value>\n      Override configuration value, nested keys are separated with `.`.\n  -h, --help\n      Print help.\n\nKeys:\n",
+    );
+
+    let defaults = Config::builder()
+        .add_source(File::from_str(default_config, FileFormat::Toml))
+        .build()
+        .and_then(|config| config.try_deserialize::<serde_json::Value>())
+        .unwrap_or_default();
+
+    let mut keys = Vec::new();
+    flatten("", &defaults, &mut keys);
+
+    for (key, default) in keys {
+        let _ = writeln!(help, "  --{key} (default: {default})");
+    }
+
+    help
+}
+
+fn flatten(prefix: &str, value: &serde_json::Value, keys: &mut Vec<(String, String)>) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map {
+                let key = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                flatten(&key, value, keys);
+            }
+        }
+        serde_json::Value::String(str) => keys.push((prefix.to_owned(), str.clone())),
+        value => keys.push((prefix.to_owned(), value.to_string())),
+    }
+}
+
+/// Reads `--key value`, `--key=value` and `--set key=value` arguments, nested keys are separated with `.`.\
+/// Argument without value is read as `true`. `--key` which is not described by fregate [`crate::ConfigSchema`] and positional arguments are ignored,
+/// so application might parse its own flags.
+#[derive(Debug, Clone)]
+pub(crate) struct Args {
+    args: Vec<String>,
+}
+
+impl Args {
+    pub(crate) fn new(args: Vec<String>) -> Self {
+        Self { args }
+    }
+}
+
+impl Source for Args {
+    fn clone_into_box(&self) -> Box<dyn Source + Send + Sync> {
+        Box::new(self.clone())
+    }
+
+    fn collect(&self) -> Result<Map<String, Value>, ConfigError> {
+        let origin = ORIGIN.to_owned();
+        let schema = AppConfig::<Empty>::json_schema();
+        let mut values = Map::new();
+        let mut args = self.args.iter().peekable();
+
+        while let Some(arg) = args.next() {
+            if HELP_ARGS.contains(&arg.as_str()) {
+                continue;
+            }
+
+            let (key, value) = if arg == SET_ARG {
+                let set = args.next().ok_or_else(|| {
+                    ConfigError::Message(format!("Missing `key=value` after `{SET_ARG}`."))
+                })?;
+                let (key, value) = set.split_once('=').ok_or_else(|| {
+                    ConfigError::Message(format!(
+                        "Invalid `{SET_ARG} {set}`, expected `{SET_ARG} key=value`."
+                    ))
+                })?;
+                (key.to_owned(), value.to_owned())
+            } else if let Some(arg) = arg.strip_prefix("--") {
+                let key = arg.split_once('=').map_or(arg, |(key, _)| key);
+                let path = key.split('.').collect::<Vec<_>>();
+                if !is_known(&schema, &path) {
+                    continue;
+                }
+
+                match arg.split_once('=') {
+                    Some((key, value)) => (key.to_owned(), value.to_owned()),
+                    None => {
+                        let value = args
+                            .next_if(|value| !value.starts_with("--"))
+                            .cloned()
+                            .unwrap_or_else(|| true.to_string());
+                        (arg.to_owned(), value)
+                    }
+                }
+            } else {
+                continue;
+            };
+
+            values.insert(
+                key.to_lowercase(),
+                Value::new(Some(&origin), parse_value(value)),
+            );
+        }
+
+        Ok(values)
+    }
+}
+
+/// Parses value the same way as environment variables with `try_parsing`.
+pub(crate) fn parse_value(value: String) -> ValueKind {
+    if let Ok(value) = value.to_lowercase().parse::<bool>() {
+        ValueKind::Boolean(value)
+    } else if let Ok(value) = value.parse::<i64>() {
+        ValueKind::I64(value)
+    } else if let Ok(value) = value.parse::<f64>() {
+        ValueKind::Float(value)
+    } else {
+        ValueKind::String(value)
+    }
+}
@@ -0,0 +1,71 @@
+use crate::configuration::interpolation::Interpolated;
+use config::{Config, ConfigError, File, Map, Source, Value};
+use std::path::{Path, PathBuf};
+
+const DATA_DIR: &str = "..data";
+const EXTENSIONS: [&str; 4] = ["toml", "yaml", "yml", "json"];
+
+/// Merges every `toml`, `yaml` and `json` file in directory in lexical order, later files override earlier ones.\
+/// If directory has Kubernetes `..data` symlink, files are read from its target so swapped symlink is read as one update.
+#[derive(Debug, Clone)]
+pub(crate) struct ConfDir {
+    path: PathBuf,
+}
+
+impl ConfDir {
+    pub(crate) fn new(path: &str) -> Self {
+        Self {
+            path: PathBuf::from(path),
+        }
+    }
+}
+
+impl Source for ConfDir {
+    fn clone_into_box(&self) -> Box<dyn Source + Send + Sync> {
+        Box::new(self.clone())
+    }
+
+    fn collect(&self) -> Result<Map<String, Value>, ConfigError> {
+        let files = conf_files(&self.path)?;
+
+        files
+            .into_iter()
+            .fold(Config::builder(), |builder, file| {
+                let name = format!("file `{}`", file.display());
+                builder.add_source(Interpolated::new(name, File::from(file)))
+            })
+            .build()?
+            .collect()
+    }
+}
+
+/// Returns supported files of directory in lexical order, resolving `..data` symlink once.
+pub(crate) fn conf_files(dir: &Path) -> Result<Vec<PathBuf>, ConfigError> {
+    let data_dir = dir.join(DATA_DIR);
+    let dir = std::fs::canonicalize(&data_dir).unwrap_or_else(|_| dir.to_path_buf());
+
+    let entries = std::fs::read_dir(&dir).map_err(|err| {
+        ConfigError::Message(format!(
+            "Can't read configuration directory `{}`: `{err}`.",
+            dir.display()
+        ))
+    })?;
+
+    let mut files = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            let visible = path
+                .file_name()
+                .is_some_and(|name| !name.to_string_lossy().starts_with('.'));
+            let supported = path.extension().is_some_and(|extension| {
+                EXTENSIONS.iter().any(|supported| extension == *supported)
+            });
+
+            visible && supported && path.is_file()
+        })
+        .collect::<Vec<_>>();
+
+    files.sort();
+    Ok(files)
+}
@@ -0,0 +1,157 @@
+use config::{ConfigError, Environment, Map, Source, Value};
+use std::path::PathBuf;
+
+const EXPORT_PREFIX: &str = "export ";
+
+/// Reads variables from `.env` file and maps them the same way as [`Environment`] with given prefix.\
+/// Process environment is not changed, missing file is read as empty.
+#[derive(Debug, Clone)]
+pub(crate) struct DotEnv {
+    path: PathBuf,
+    prefix: String,
+    separator: String,
+}
+
+impl DotEnv {
+    pub(crate) fn new(path: &str, prefix: &str, separator: &str) -> Self {
+        Self {
+            path: PathBuf::from(path),
+            prefix: prefix.to_owned(),
+            separator: separator.to_owned(),
+        }
+    }
+}
+
+impl Source for DotEnv {
+    fn clone_into_box(&self) -> Box<dyn Source + Send + Sync> {
+        Box::new(self.clone())
+    }
+
+    fn collect(&self) -> Result<Map<String, Value>, ConfigError> {
+        let content = match std::fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Map::new()),
+            Err(err) => {
+                return Err(ConfigError::Message(format!(
+                    "Can't read `{}`: `{err}`.",
+                    self.path.display()
+                )))
+            }
+        };
+
+        let vars = parse_dotenv(&content).map_err(|(line, message)| {
+            ConfigError::Message(format!("{}:{line}: {message}.", self.path.display()))
+        })?;
+
+        Environment::with_prefix(&self.prefix)
+            .try_parsing(true)
+            .separator(&self.separator)
+            .source(Some(vars))
+            .collect()
+    }
+}
+
+/// Parses `KEY=value` lines, `export` prefix, `#` comments, single-quoted literal and double-quoted escaped values.\
+/// Returns line number and message on error.
+pub(crate) fn parse_dotenv(content: &str) -> Result<Map<String, String>, (usize, String)> {
+    let mut vars = Map::new();
+
+    for (index, line) in content.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let line = line.strip_prefix(EXPORT_PREFIX).unwrap_or(line);
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| (line_number, format!("expected `KEY=value`, got `{line}`")))?;
+        let key = key.trim();
+
+        if key.is_empty() || key.contains(char::is_whitespace) {
+            return Err((line_number, format!("invalid variable name `{key}`")));
+        }
+
+        let value = parse_value(value.trim()).map_err(|message| (line_number, message))?;
+        vars.insert(key.to_owned(), value);
+    }
+
+    Ok(vars)
+}
+
+fn parse_value(value: &str) -> Result<String, String> {
+    if let Some(quoted) = value.strip_prefix('\'') {
+        let (value, _) = quoted
+            .split_once('\'')
+            .ok_or_else(|| "unterminated single quote".to_owned())?;
+        return Ok(value.to_owned());
+    }
+
+    if let Some(quoted) = value.strip_prefix('"') {
+        let mut unescaped = String::new();
+        let mut chars = quoted.chars();
+
+        while let Some(char) = chars.next() {
+            match char {
+                '"' => return Ok(unescaped),
+                '\\' => match chars.next() {
+                    Some('n') => unescaped.push('\n'),
+                    Some('t') => unescaped.push('\t'),
+                    Some(char) => unescaped.push(char),
+                    None => break,
+                },
+                char => unescaped.push(char),
+            }
+        }
+
+        return Err("unterminated double quote".to_owned());
+    }
+
+    let value = value
+        .split_once(" #")
+        .map_or(value, |(value, _comment)| value)
+        .trim_end();
+
+    Ok(value.to_owned())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod dotenv_test {
+    use super::*;
+
+    #[test]
+    fn parse_dotenv_test() {
+        let vars = parse_dotenv(
+            r#"
+            # comment
+            TEST_PORT=9000
+            export TEST_LOG_LEVEL = debug # inline comment
+            TEST_SINGLE='literal \n # value'
+            TEST_DOUBLE="line\nnext"
+            TEST_EMPTY=
+            "#,
+        )
+        .expect("Failed to parse .env");
+
+        assert_eq!(vars.get("TEST_PORT").map(String::as_str), Some("9000"));
+        assert_eq!(
+            vars.get("TEST_LOG_LEVEL").map(String::as_str),
+            Some("debug")
+        );
+        assert_eq!(
+            vars.get("TEST_SINGLE").map(String::as_str),
+            Some("literal \\n # value")
+        );
+        assert_eq!(
+            vars.get("TEST_DOUBLE").map(String::as_str),
+            Some("line\nnext")
+        );
+        assert_eq!(vars.get("TEST_EMPTY").map(String::as_str), Some(""));
+
+        assert_eq!(parse_dotenv("\nINVALID").map_err(|(line, _)| line), Err(2));
+        assert!(parse_dotenv("KEY=\"unterminated").is_err());
+    }
+}
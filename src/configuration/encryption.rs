@@ -0,0 +1,220 @@
+use crate::configuration::memory::Values;
+use crate::error::{Error, Result};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use config::{Config, ConfigError, ValueKind};
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde_json::Value;
+
+const ENCRYPTED_PREFIX: &str = "ENC[";
+const AES256_GCM_PREFIX: &str = "ENC[AES256_GCM,";
+const ENCRYPTED_SUFFIX: &str = "]";
+const KEY_LEN: usize = 32;
+
+/// Environment variable with base64 encoded 32 bytes key used if [`crate::AppConfigBuilder::decryption_key`] is not set.
+pub const ENCRYPTION_KEY_ENV: &str = "APP_CONFIG_KEY";
+/// Environment variable with path to file with base64 encoded key, used if [`ENCRYPTION_KEY_ENV`] is not set.
+pub const ENCRYPTION_KEY_FILE_ENV: &str = "APP_CONFIG_KEY_FILE";
+
+/// Where to read base64 encoded 32 bytes AES-256-GCM key from, see [`crate::AppConfigBuilder::decryption_key`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecryptionKey {
+    /// environment variable with key
+    Env(String),
+    /// file with key
+    File(String),
+}
+
+impl DecryptionKey {
+    fn read(&self) -> std::result::Result<LessSafeKey, String> {
+        let encoded = match self {
+            DecryptionKey::Env(var) => {
+                std::env::var(var).map_err(|err| format!("can't read key from `{var}`: `{err}`"))?
+            }
+            DecryptionKey::File(path) => std::fs::read_to_string(path)
+                .map_err(|err| format!("can't read key from `{path}`: `{err}`"))?,
+        };
+
+        parse_key(&encoded)
+    }
+
+    /// Returns key set by [`ENCRYPTION_KEY_ENV`] or [`ENCRYPTION_KEY_FILE_ENV`].
+    fn from_env() -> Option<Self> {
+        if std::env::var_os(ENCRYPTION_KEY_ENV).is_some() {
+            Some(DecryptionKey::Env(ENCRYPTION_KEY_ENV.to_owned()))
+        } else {
+            std::env::var(ENCRYPTION_KEY_FILE_ENV)
+                .ok()
+                .map(DecryptionKey::File)
+        }
+    }
+}
+
+fn parse_key(encoded: &str) -> std::result::Result<LessSafeKey, String> {
+    let bytes = STANDARD
+        .decode(encoded.trim())
+        .map_err(|err| format!("key is not valid base64: `{err}`"))?;
+
+    if bytes.len() != KEY_LEN {
+        return Err(format!("key must be {KEY_LEN} bytes, got {}", bytes.len()));
+    }
+
+    UnboundKey::new(&AES_256_GCM, &bytes)
+        .map(LessSafeKey::new)
+        .map_err(|_| "invalid key".to_owned())
+}
+
+/// Returns base64 encoded random 32 bytes key.
+pub fn generate_encryption_key() -> Result<String> {
+    let mut key = [0; KEY_LEN];
+    SystemRandom::new()
+        .fill(&mut key)
+        .map_err(|_| Error::EncryptionError("can't generate random key".to_owned()))?;
+
+    Ok(STANDARD.encode(key))
+}
+
+/// Encrypts value with base64 encoded key into `ENC[AES256_GCM,...]` which is decrypted on [`crate::AppConfigBuilder::build`].
+/// ```
+/// use fregate::{encrypt_value, generate_encryption_key};
+///
+/// let key = generate_encryption_key().unwrap();
+/// let encrypted = encrypt_value(&key, "password").unwrap();
+///
+/// assert!(encrypted.starts_with("ENC[AES256_GCM,"));
+/// ```
+pub fn encrypt_value(key: &str, value: &str) -> Result<String> {
+    let key = parse_key(key).map_err(Error::EncryptionError)?;
+
+    let mut nonce = [0; NONCE_LEN];
+    SystemRandom::new()
+        .fill(&mut nonce)
+        .map_err(|_| Error::EncryptionError("can't generate nonce".to_owned()))?;
+
+    let mut encrypted = value.as_bytes().to_vec();
+    key.seal_in_place_append_tag(
+        Nonce::assume_unique_for_key(nonce),
+        Aad::empty(),
+        &mut encrypted,
+    )
+    .map_err(|_| Error::EncryptionError("can't encrypt value".to_owned()))?;
+
+    let mut payload = nonce.to_vec();
+    payload.extend(encrypted);
+
+    Ok(format!(
+        "{AES256_GCM_PREFIX}{}{ENCRYPTED_SUFFIX}",
+        STANDARD.encode(payload)
+    ))
+}
+
+fn decrypt_value(key: &LessSafeKey, value: &str) -> std::result::Result<String, String> {
+    let payload = value
+        .strip_prefix(AES256_GCM_PREFIX)
+        .and_then(|value| value.strip_suffix(ENCRYPTED_SUFFIX))
+        .ok_or_else(|| "unsupported encryption, expected `ENC[AES256_GCM,...]`".to_owned())?;
+    let mut payload = STANDARD
+        .decode(payload)
+        .map_err(|err| format!("value is not valid base64: `{err}`"))?;
+
+    if payload.len() < NONCE_LEN {
+        return Err("value is too short".to_owned());
+    }
+
+    let mut encrypted = payload.split_off(NONCE_LEN);
+    let nonce =
+        Nonce::try_assume_unique_for_key(&payload).map_err(|_| "invalid nonce".to_owned())?;
+    let decrypted = key
+        .open_in_place(nonce, Aad::empty(), &mut encrypted)
+        .map_err(|_| "can't decrypt value, wrong key or corrupted value".to_owned())?;
+
+    String::from_utf8(decrypted.to_vec())
+        .map_err(|err| format!("decrypted value is not UTF-8: `{err}`"))
+}
+
+/// Replaces every `ENC[...]` string of config with decrypted string and returns keys of decrypted values, see [`crate::SensitiveKeys`].
+pub(crate) fn decrypt_config(
+    config: Config,
+    key: Option<&DecryptionKey>,
+) -> std::result::Result<(Config, Vec<String>), ConfigError> {
+    let tree = config.clone().try_deserialize::<Value>()?;
+
+    let mut encrypted = Vec::new();
+    find_encrypted(String::new(), &tree, &mut encrypted);
+
+    if encrypted.is_empty() {
+        return Ok((config, Vec::new()));
+    }
+
+    let key = key
+        .cloned()
+        .or_else(DecryptionKey::from_env)
+        .ok_or_else(|| {
+            ConfigError::Message(format!(
+                "Configuration has encrypted values but no key is set in `{ENCRYPTION_KEY_ENV}` or `{ENCRYPTION_KEY_FILE_ENV}`."
+            ))
+        })?
+        .read()
+        .map_err(|err| ConfigError::Message(format!("Can't read decryption key: {err}.")))?;
+
+    let mut decrypted = config::Map::new();
+    for (path, value) in encrypted {
+        let value = decrypt_value(&key, value).map_err(|err| {
+            ConfigError::Message(format!("Can't decrypt value at `{path}`: {err}."))
+        })?;
+
+        decrypted.insert(path, config::Value::new(None, ValueKind::String(value)));
+    }
+
+    let keys = decrypted.keys().cloned().collect();
+    let config = Config::builder()
+        .add_source(config)
+        .add_source(Values::new(decrypted))
+        .build()?;
+
+    Ok((config, keys))
+}
+
+fn find_encrypted<'a>(path: String, value: &'a Value, encrypted: &mut Vec<(String, &'a str)>) {
+    match value {
+        Value::String(str) if str.starts_with(ENCRYPTED_PREFIX) => encrypted.push((path, str)),
+        Value::Object(map) => {
+            for (key, value) in map {
+                let path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                find_encrypted(path, value, encrypted);
+            }
+        }
+        Value::Array(values) => {
+            for (index, value) in values.iter().enumerate() {
+                find_encrypted(format!("{path}[{index}]"), value, encrypted);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod encryption_test {
+    use super::*;
+
+    #[test]
+    fn encrypt_decrypt_test() {
+        let encoded = generate_encryption_key().unwrap();
+        let key = parse_key(&encoded).unwrap();
+
+        let encrypted = encrypt_value(&encoded, "password").unwrap();
+        assert_eq!(decrypt_value(&key, &encrypted).as_deref(), Ok("password"));
+
+        let other = parse_key(&generate_encryption_key().unwrap()).unwrap();
+        assert!(decrypt_value(&other, &encrypted).is_err());
+        assert!(decrypt_value(&key, "ENC[AES256_GCM,AAAA]").is_err());
+        assert!(decrypt_value(&key, "ENC[PGP,AAAA]").is_err());
+        assert!(parse_key("c2hvcnQ=").is_err());
+    }
+}
@@ -0,0 +1,150 @@
+use config::{ConfigError, Map, Source, Value, ValueKind};
+
+const ESCAPED: &str = "$${";
+const OPEN: &str = "${";
+const CLOSE: char = '}';
+const DEFAULT_SEPARATOR: &str = ":-";
+
+/// Expands `${VAR}` and `${VAR:-default}` in string values of wrapped source, `$${` is read as literal `${`.\
+/// Expanded values stay strings, e.g. `"${PIN}"` with `PIN=0123` is read as `"0123"`, and are converted by type which reads them,
+/// so `port = "${PORT:-8000}"` is still read as number.
+#[derive(Debug, Clone)]
+pub(crate) struct Interpolated<S> {
+    name: String,
+    source: S,
+}
+
+impl<S> Interpolated<S> {
+    pub(crate) fn new(name: String, source: S) -> Self {
+        Self { name, source }
+    }
+}
+
+impl<S> Source for Interpolated<S>
+where
+    S: Source + Clone + Send + Sync + 'static,
+{
+    fn clone_into_box(&self) -> Box<dyn Source + Send + Sync> {
+        Box::new(self.clone())
+    }
+
+    fn collect(&self) -> Result<Map<String, Value>, ConfigError> {
+        let mut values = self.source.collect()?;
+
+        for (key, value) in &mut values {
+            interpolate_value(value, key).map_err(|Unresolved { var, key }| {
+                ConfigError::Message(format!(
+                    "Unresolved environment variable `{var}` in {} at key `{key}`.",
+                    self.name
+                ))
+            })?;
+        }
+
+        Ok(values)
+    }
+}
+
+struct Unresolved {
+    var: String,
+    key: String,
+}
+
+fn interpolate_value(value: &mut Value, key: &str) -> Result<(), Unresolved> {
+    match &mut value.kind {
+        ValueKind::String(str) => {
+            *str = interpolate(str).map_err(|var| Unresolved {
+                var,
+                key: key.to_owned(),
+            })?;
+        }
+        ValueKind::Table(table) => {
+            for (nested, value) in table {
+                interpolate_value(value, &format!("{key}.{nested}"))?;
+            }
+        }
+        ValueKind::Array(array) => {
+            for (index, value) in array.iter_mut().enumerate() {
+                interpolate_value(value, &format!("{key}[{index}]"))?;
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Returns expanded string or name of unresolved variable.
+pub(crate) fn interpolate(str: &str) -> Result<String, String> {
+    let mut expanded = String::with_capacity(str.len());
+    let mut rest = str;
+
+    while let Some(position) = rest.find('$') {
+        let (before, after) = rest.split_at(position);
+        expanded.push_str(before);
+
+        if let Some(after) = after.strip_prefix(ESCAPED) {
+            expanded.push_str(OPEN);
+            rest = after;
+        } else if let Some((expression, after)) = after
+            .strip_prefix(OPEN)
+            .and_then(|after| after.split_once(CLOSE))
+        {
+            let (var, default) = match expression.split_once(DEFAULT_SEPARATOR) {
+                Some((var, default)) => (var, Some(default)),
+                None => (expression, None),
+            };
+
+            let value = match (std::env::var(var).ok(), default) {
+                (Some(value), Some(default)) if value.is_empty() => default.to_owned(),
+                (Some(value), _) => value,
+                (None, Some(default)) => default.to_owned(),
+                (None, None) => return Err(var.to_owned()),
+            };
+
+            expanded.push_str(&value);
+            rest = after;
+        } else {
+            expanded.push('$');
+            rest = after.get(1..).unwrap_or_default();
+        }
+    }
+
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+#[cfg(test)]
+mod interpolation_test {
+    use super::*;
+
+    #[test]
+    fn interpolate_test() {
+        std::env::set_var("INTERPOLATION_TEST_HOST", "localhost");
+        std::env::set_var("INTERPOLATION_TEST_EMPTY", "");
+
+        assert_eq!(
+            interpolate("postgres://${INTERPOLATION_TEST_HOST}:5432").as_deref(),
+            Ok("postgres://localhost:5432")
+        );
+        assert_eq!(
+            interpolate("${INTERPOLATION_TEST_MISSING:-default}").as_deref(),
+            Ok("default")
+        );
+        assert_eq!(
+            interpolate("${INTERPOLATION_TEST_EMPTY:-default}").as_deref(),
+            Ok("default")
+        );
+        assert_eq!(
+            interpolate("${INTERPOLATION_TEST_EMPTY}").as_deref(),
+            Ok("")
+        );
+        assert_eq!(
+            interpolate("$${INTERPOLATION_TEST_HOST} costs $5").as_deref(),
+            Ok("${INTERPOLATION_TEST_HOST} costs $5")
+        );
+        assert_eq!(
+            interpolate("${INTERPOLATION_TEST_MISSING}"),
+            Err("INTERPOLATION_TEST_MISSING".to_owned())
+        );
+    }
+}
@@ -0,0 +1,359 @@
+use crate::configuration::units::parse_size;
+use crate::extensions::DeserializeExt;
+use crate::ConfigSchema;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer};
+use serde_json::{json, Value};
+use std::path::PathBuf;
+
+const OUTPUT_PTR: &str = "/output";
+const FILE_PTR: &str = "/file";
+const SYSLOG_PTR: &str = "/syslog";
+const FACILITY_PTR: &str = "/facility";
+const NAME_PTR: &str = "/name";
+const LEVEL_PTR: &str = "/level";
+const FORMAT_PTR: &str = "/format";
+const PATH_PTR: &str = "/path";
+const ROTATION_PTR: &str = "/rotation";
+const RETAIN_PTR: &str = "/retain";
+const COMPRESS_PTR: &str = "/compress";
+const DEFAULT_SYSLOG_PATH: &str = "/dev/log";
+const DEFAULT_SYSLOG_FACILITY: &str = "user";
+const SYSLOG_FACILITIES: [(&str, u8); 20] = [
+    ("kern", 0),
+    ("user", 1),
+    ("mail", 2),
+    ("daemon", 3),
+    ("auth", 4),
+    ("syslog", 5),
+    ("lpr", 6),
+    ("news", 7),
+    ("uucp", 8),
+    ("cron", 9),
+    ("authpriv", 10),
+    ("ftp", 11),
+    ("local0", 16),
+    ("local1", 17),
+    ("local2", 18),
+    ("local3", 19),
+    ("local4", 20),
+    ("local5", 21),
+    ("local6", 22),
+    ("local7", 23),
+];
+
+/// Where log lines are written, read from `log.output`:
+/// ```toml
+/// [log]
+/// output = "file"          # "stdout" (default), "stderr", "file" or "syslog"
+///
+/// [log.file]
+/// path = "/var/log/app.log"
+/// rotation = "daily"       # "never", "hourly", "daily" (default) or size like "100MiB"
+/// retain = 7               # number of rotated files to keep, all are kept if unset
+/// compress = true          # gzip rotated files
+///
+/// [log.syslog]
+/// path = "/dev/log"        # default
+/// facility = "local0"      # "user" (default), "daemon", "local0" - "local7" or other RFC 5424 facility
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum LogOutput {
+    /// [`std::io::stdout`]
+    #[default]
+    Stdout,
+    /// [`std::io::stderr`]
+    Stderr,
+    /// file with rotation, see [`crate::observability::RollingFileWriter`]
+    File(LogFileConfig),
+    /// local syslog Unix socket, see [`crate::observability::SyslogWriter`]
+    Syslog(SyslogConfig),
+}
+
+/// Format of log lines, read from `log.format`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    /// single-line JSON
+    #[default]
+    Json,
+    /// colored multi-line text with every field on its own line
+    Pretty,
+    /// colored single-line text with fields as `key=value`
+    Compact,
+}
+
+/// Additional log sink with its own level, output and format, read from `log.sinks`.\
+/// Level of sink might be changed in runtime by its name, see [`crate::observability::LOG_SINK_HANDLES`].
+/// ```toml
+/// [[log.sinks]]
+/// name = "debug_file"
+/// level = "debug"
+/// output = "file"           # same as `log.output`
+/// format = "compact"        # same as `log.format`
+/// file = { path = "/var/log/app.log", rotation = "hourly" }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogSinkConfig {
+    /// unique sink name
+    pub name: String,
+    /// log level read to string and later parsed into EnvFilter
+    pub level: String,
+    /// where sink writes logs
+    pub output: LogOutput,
+    /// format of sink log lines
+    pub format: LogFormat,
+}
+
+/// `log.file` configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogFileConfig {
+    /// Path of active log file, rotated files are put next to it.
+    pub path: PathBuf,
+    /// When active file is rotated.
+    pub rotation: LogRotation,
+    /// Number of rotated files to keep, oldest are removed.
+    pub retain: Option<usize>,
+    /// Compress rotated files with gzip.
+    pub compress: bool,
+}
+
+/// `log.syslog` configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyslogConfig {
+    /// Unix datagram socket, `/dev/log` by default.
+    pub path: PathBuf,
+    /// Facility code combined with event level into priority of each line, `user` (1) by default.
+    pub facility: u8,
+}
+
+impl Default for SyslogConfig {
+    fn default() -> Self {
+        Self {
+            path: PathBuf::from(DEFAULT_SYSLOG_PATH),
+            facility: 1,
+        }
+    }
+}
+
+/// Log file rotation policy.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogRotation {
+    /// file is never rotated
+    Never,
+    /// file is rotated at the start of every UTC hour
+    Hourly,
+    /// file is rotated at the start of every UTC day
+    #[default]
+    Daily,
+    /// file is rotated before it exceeds given number of bytes
+    Size(usize),
+}
+
+impl<'de> Deserialize<'de> for LogOutput {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let config = Value::deserialize(deserializer)?;
+
+        let output = config
+            .pointer_and_deserialize::<String, D::Error>(OUTPUT_PTR)
+            .ok();
+
+        match output.as_deref() {
+            None | Some("stdout") => Ok(LogOutput::Stdout),
+            Some("stderr") => Ok(LogOutput::Stderr),
+            Some("file") => config
+                .pointer_and_deserialize(FILE_PTR)
+                .map(LogOutput::File),
+            Some("syslog") => config
+                .pointer(SYSLOG_PTR)
+                .map(SyslogConfig::deserialize)
+                .transpose()
+                .map_err(D::Error::custom)
+                .map(|syslog| LogOutput::Syslog(syslog.unwrap_or_default())),
+            Some(output) => Err(D::Error::custom(format!(
+                "unknown log output `{output}`, expected `stdout`, `stderr`, `file` or `syslog`"
+            ))),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for LogSinkConfig {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let config = Value::deserialize(deserializer)?;
+
+        let name = config.pointer_and_deserialize(NAME_PTR)?;
+        let level = config.pointer_and_deserialize(LEVEL_PTR)?;
+        let output = LogOutput::deserialize(&config).map_err(D::Error::custom)?;
+        let format = config
+            .pointer(FORMAT_PTR)
+            .map(LogFormat::deserialize)
+            .transpose()
+            .map_err(D::Error::custom)?
+            .unwrap_or_default();
+
+        Ok(LogSinkConfig {
+            name,
+            level,
+            output,
+            format,
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for LogFileConfig {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let config = Value::deserialize(deserializer)?;
+
+        let path = config.pointer_and_deserialize(PATH_PTR)?;
+        let rotation = config
+            .pointer(ROTATION_PTR)
+            .map(LogRotation::deserialize)
+            .transpose()
+            .map_err(D::Error::custom)?
+            .unwrap_or_default();
+        let retain = config
+            .pointer_and_deserialize::<_, D::Error>(RETAIN_PTR)
+            .ok();
+        let compress = config
+            .pointer_and_deserialize::<_, D::Error>(COMPRESS_PTR)
+            .unwrap_or_default();
+
+        Ok(LogFileConfig {
+            path,
+            rotation,
+            retain,
+            compress,
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for SyslogConfig {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let config = Value::deserialize(deserializer)?;
+
+        let path = config
+            .pointer_and_deserialize::<_, D::Error>(PATH_PTR)
+            .unwrap_or_else(|_| PathBuf::from(DEFAULT_SYSLOG_PATH));
+        let facility = config
+            .pointer(FACILITY_PTR)
+            .map(String::deserialize)
+            .transpose()
+            .map_err(D::Error::custom)?
+            .unwrap_or_else(|| DEFAULT_SYSLOG_FACILITY.to_owned());
+        let facility = SYSLOG_FACILITIES
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(&facility))
+            .map(|(_, code)| *code)
+            .ok_or_else(|| {
+                D::Error::custom(format!(
+                    "unknown syslog facility `{facility}`, expected one of: {}",
+                    SYSLOG_FACILITIES.map(|(name, _)| name).join(", ")
+                ))
+            })?;
+
+        Ok(SyslogConfig { path, facility })
+    }
+}
+
+impl<'de> Deserialize<'de> for LogFormat {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let format = String::deserialize(deserializer)?;
+
+        match format.to_lowercase().as_str() {
+            "json" => Ok(LogFormat::Json),
+            "pretty" => Ok(LogFormat::Pretty),
+            "compact" => Ok(LogFormat::Compact),
+            _ => Err(D::Error::custom(format!(
+                "unknown log format `{format}`, expected `json`, `pretty` or `compact`"
+            ))),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for LogRotation {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let rotation = String::deserialize(deserializer)?;
+
+        match rotation.to_lowercase().as_str() {
+            "never" => Ok(LogRotation::Never),
+            "hourly" => Ok(LogRotation::Hourly),
+            "daily" => Ok(LogRotation::Daily),
+            size => parse_size(size).map(LogRotation::Size).map_err(|err| {
+                D::Error::custom(format!(
+                    "invalid log rotation `{rotation}`, expected `never`, `hourly`, `daily` or size: {err}"
+                ))
+            }),
+        }
+    }
+}
+
+impl ConfigSchema for LogFileConfig {
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "description": "log file used if `log.output` is `file`",
+            "properties": {
+                "path": { "type": "string", "description": "active log file, rotated files are put next to it", "examples": ["/var/log/app.log"] },
+                "rotation": {
+                    "type": "string",
+                    "description": "`never`, `hourly`, `daily` or size like `100MiB`",
+                    "default": "daily",
+                    "examples": ["daily", "100MiB"]
+                },
+                "retain": { "type": "integer", "minimum": 0, "description": "number of rotated files to keep, all are kept if unset", "examples": [7] },
+                "compress": { "type": "boolean", "description": "gzip rotated files", "default": false }
+            },
+            "required": ["path"]
+        })
+    }
+}
+
+impl ConfigSchema for LogSinkConfig {
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string", "description": "unique sink name" },
+                "level": { "type": "string", "description": "log filter directives of this sink" },
+                "output": { "type": "string", "enum": ["stdout", "stderr", "file", "syslog"] },
+                "format": { "type": "string", "enum": ["json", "pretty", "compact"], "default": "json" },
+                "file": LogFileConfig::schema(),
+                "syslog": syslog_schema()
+            },
+            "required": ["name", "level"]
+        })
+    }
+}
+
+pub(crate) fn syslog_schema() -> Value {
+    json!({
+        "type": "object",
+        "description": "syslog socket used if output is `syslog`",
+        "properties": {
+            "path": { "type": "string", "description": "Unix datagram socket", "default": DEFAULT_SYSLOG_PATH },
+            "facility": {
+                "type": "string",
+                "enum": SYSLOG_FACILITIES.map(|(name, _)| name),
+                "description": "facility combined with event level into syslog priority",
+                "default": DEFAULT_SYSLOG_FACILITY
+            }
+        }
+    })
+}
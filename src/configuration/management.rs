@@ -1,26 +1,39 @@
-use crate::extensions::DeserializeExt;
+mod auth;
+
+pub use auth::*;
+
 use crate::static_assert;
+use crate::{ConfigSchema, Validate, ValidationErrors};
 use serde::de::{Error, Unexpected};
 use serde::{Deserialize, Deserializer};
-use serde_json::Value;
+use serde_json::{json, Value};
 
 const HEALTH_ENDPOINT: &str = "/health";
 const LIVE_ENDPOINT: &str = "/live";
 const READY_ENDPOINT: &str = "/ready";
 const METRICS_ENDPOINT: &str = "/metrics";
 const VERSION_ENDPOINT: &str = "/version";
+const ROUTES_ENDPOINT: &str = "/routes";
 
 const HEALTH_PTR: &str = "/health";
 const LIVE_PTR: &str = "/live";
 const READY_PTR: &str = "/ready";
 const METRICS_PTR: &str = "/metrics";
 const VERSION_PTR: &str = "/version";
+const ROUTES_PTR: &str = "/routes";
+const PATH_PTR: &str = "/path";
+const ENABLED_PTR: &str = "/enabled";
 
 #[derive(Debug, Default, Clone, Deserialize)]
-/// [`Management`](https://github.com/elefant-dev/fregate-rs/blob/main/src/application/management.rs) configuration. Currently only endpoints configuration is supported.
+#[serde(default)]
+/// [`Management`](https://github.com/elefant-dev/fregate-rs/blob/main/src/application/management.rs) configuration.
 pub struct ManagementConfig {
+    /// prefix to nest all management endpoints under, e.g. `/_/`.
+    pub prefix: Option<Endpoint>,
     /// health and metrics endpoints.
     pub endpoints: Endpoints,
+    /// authentication and IP allow-list for management endpoints.
+    pub auth: ManagementAuth,
 }
 
 /// By default endpoints are:
@@ -30,6 +43,7 @@ pub struct ManagementConfig {
 /// const READY_ENDPOINT: &str = "/ready";
 /// const METRICS_ENDPOINT: &str = "/metrics";
 /// const VERSION_ENDPOINT: &str = "/{component_name}/version";
+/// const ROUTES_ENDPOINT: &str = "/routes";
 /// ```
 /// You might want to change those:\
 /// Example:
@@ -47,10 +61,10 @@ pub struct ManagementConfig {
 /// async fn main() {
 ///     std::env::set_var("TEST_MANAGEMENT_ENDPOINTS_METRICS", "/observability");
 ///     std::env::set_var("TEST_MANAGEMENT_ENDPOINTS_HEALTH", "///also_valid");
-///     // this is invalid default "/live" endpoint will be used.
-///     std::env::set_var("TEST_MANAGEMENT_ENDPOINTS_LIVE", "invalid");
-///     // this is invalid default "/ready" endpoint will be used.
-///     std::env::set_var("TEST_MANAGEMENT_ENDPOINTS_READY", "");
+///     // this is invalid and config will fail to build.
+///     // std::env::set_var("TEST_MANAGEMENT_ENDPOINTS_LIVE", "invalid");
+///     // version endpoint will not be served.
+///     std::env::set_var("TEST_MANAGEMENT_ENDPOINTS_VERSION_ENABLED", "false");
 ///
 ///     let config: AppConfig = bootstrap([ConfigSource::EnvPrefix("TEST")]).unwrap();
 ///
@@ -61,6 +75,12 @@ pub struct ManagementConfig {
 ///         .unwrap();
 /// }
 /// ```
+/// Each endpoint might be set either as a path or as a table with `path` and `enabled` keys:
+/// ```toml
+/// [management.endpoints]
+/// health = "/healthz"
+/// metrics = { path = "/metrics", enabled = false }
+/// ```
 #[derive(Debug, Clone)]
 pub struct Endpoints {
     /// health endpoint
@@ -73,6 +93,8 @@ pub struct Endpoints {
     pub metrics: Endpoint,
     /// version endpoint
     pub version: Endpoint,
+    /// endpoint listing served routes, disabled by default
+    pub routes: Endpoint,
 }
 
 #[allow(clippy::indexing_slicing)]
@@ -86,24 +108,16 @@ impl<'de> Deserialize<'de> for Endpoints {
         static_assert!(READY_ENDPOINT.as_bytes()[0] == b'/');
         static_assert!(METRICS_ENDPOINT.as_bytes()[0] == b'/');
         static_assert!(VERSION_ENDPOINT.as_bytes()[0] == b'/');
+        static_assert!(ROUTES_ENDPOINT.as_bytes()[0] == b'/');
 
         let value = Value::deserialize(deserializer)?;
 
-        let health = value
-            .pointer_and_deserialize::<_, D::Error>(HEALTH_PTR)
-            .unwrap_or_else(|_| Endpoint(HEALTH_ENDPOINT.to_owned()));
-        let live = value
-            .pointer_and_deserialize::<_, D::Error>(LIVE_PTR)
-            .unwrap_or_else(|_| Endpoint(LIVE_ENDPOINT.to_owned()));
-        let ready = value
-            .pointer_and_deserialize::<_, D::Error>(READY_PTR)
-            .unwrap_or_else(|_| Endpoint(READY_ENDPOINT.to_owned()));
-        let metrics = value
-            .pointer_and_deserialize::<_, D::Error>(METRICS_PTR)
-            .unwrap_or_else(|_| Endpoint(METRICS_ENDPOINT.to_owned()));
-        let version = value
-            .pointer_and_deserialize::<_, D::Error>(VERSION_PTR)
-            .unwrap_or_else(|_| Endpoint(VERSION_ENDPOINT.to_owned()));
+        let health = deserialize_endpoint(&value, HEALTH_PTR, HEALTH_ENDPOINT, true);
+        let live = deserialize_endpoint(&value, LIVE_PTR, LIVE_ENDPOINT, true);
+        let ready = deserialize_endpoint(&value, READY_PTR, READY_ENDPOINT, true);
+        let metrics = deserialize_endpoint(&value, METRICS_PTR, METRICS_ENDPOINT, true);
+        let version = deserialize_endpoint(&value, VERSION_PTR, VERSION_ENDPOINT, true);
+        let routes = deserialize_endpoint(&value, ROUTES_PTR, ROUTES_ENDPOINT, false);
 
         Ok(Endpoints {
             health,
@@ -111,6 +125,7 @@ impl<'de> Deserialize<'de> for Endpoints {
             ready,
             metrics,
             version,
+            routes,
         })
     }
 }
@@ -123,31 +138,170 @@ impl Default for Endpoints {
         static_assert!(READY_ENDPOINT.as_bytes()[0] == b'/');
         static_assert!(METRICS_ENDPOINT.as_bytes()[0] == b'/');
         static_assert!(VERSION_ENDPOINT.as_bytes()[0] == b'/');
+        static_assert!(ROUTES_ENDPOINT.as_bytes()[0] == b'/');
 
         Self {
-            health: Endpoint(HEALTH_ENDPOINT.to_owned()),
-            live: Endpoint(LIVE_ENDPOINT.to_owned()),
-            ready: Endpoint(READY_ENDPOINT.to_owned()),
-            metrics: Endpoint(METRICS_ENDPOINT.to_owned()),
-            version: Endpoint(VERSION_ENDPOINT.to_owned()),
+            health: Endpoint::new_unchecked(HEALTH_ENDPOINT),
+            live: Endpoint::new_unchecked(LIVE_ENDPOINT),
+            ready: Endpoint::new_unchecked(READY_ENDPOINT),
+            metrics: Endpoint::new_unchecked(METRICS_ENDPOINT),
+            version: Endpoint::new_unchecked(VERSION_ENDPOINT),
+            routes: Endpoint::new_unchecked(ROUTES_ENDPOINT).with_enabled(false),
         }
     }
 }
 
+impl Validate for ManagementConfig {
+    fn validate(&self, errors: &mut ValidationErrors) {
+        errors.nested("/endpoints", &self.endpoints);
+        errors.nested("/auth", &self.auth);
+    }
+}
+
+impl Validate for Endpoints {
+    fn validate(&self, errors: &mut ValidationErrors) {
+        for (pointer, endpoint) in [
+            (HEALTH_PTR, &self.health),
+            (LIVE_PTR, &self.live),
+            (READY_PTR, &self.ready),
+            (METRICS_PTR, &self.metrics),
+            (VERSION_PTR, &self.version),
+            (ROUTES_PTR, &self.routes),
+        ] {
+            if let Some(rejected) = &endpoint.rejected {
+                errors.add(
+                    pointer,
+                    format!("invalid endpoint `{rejected}`, endpoint must start with a `/`"),
+                );
+            }
+        }
+    }
+}
+
+impl ConfigSchema for ManagementConfig {
+    fn schema() -> Value {
+        let endpoint = |description: &str, default: &str, enabled: bool| {
+            json!({
+                "description": description,
+                "default": { "path": default, "enabled": enabled },
+                "oneOf": [
+                    { "type": "string", "pattern": "^/" },
+                    {
+                        "type": "object",
+                        "properties": {
+                            "path": { "type": "string", "pattern": "^/" },
+                            "enabled": { "type": "boolean" }
+                        }
+                    }
+                ]
+            })
+        };
+
+        json!({
+            "type": "object",
+            "properties": {
+                "prefix": {
+                    "type": "string",
+                    "pattern": "^/",
+                    "description": "prefix to nest all management endpoints under",
+                    "examples": ["/_/"]
+                },
+                "endpoints": {
+                    "type": "object",
+                    "description": "each endpoint is either a path or a table with `path` and `enabled` keys",
+                    "properties": {
+                        "health": endpoint("health probe", HEALTH_ENDPOINT, true),
+                        "live": endpoint("liveness probe", LIVE_ENDPOINT, true),
+                        "ready": endpoint("readiness probe", READY_ENDPOINT, true),
+                        "metrics": endpoint("metrics in Prometheus or OpenMetrics format", METRICS_ENDPOINT, true),
+                        "version": endpoint("component version, served under `/{component_name}`", VERSION_ENDPOINT, true),
+                        "routes": endpoint("served routes, disabled by default, protect it with `auth`", ROUTES_ENDPOINT, false)
+                    }
+                },
+                "auth": ManagementAuth::schema()
+            }
+        })
+    }
+}
+
+/// Reads endpoint set either as a path or as a table with `path` and `enabled` keys.
+/// Falls back to default path if path is missing or invalid, invalid path is reported by [`Validate`].
+/// Endpoint which is set without `enabled` key is enabled, missing one is enabled if `default_enabled` is set.
+fn deserialize_endpoint(
+    value: &Value,
+    pointer: &'static str,
+    default: &str,
+    default_enabled: bool,
+) -> Endpoint {
+    let endpoint = value.pointer(pointer);
+    let enabled = match endpoint {
+        Some(endpoint) => endpoint
+            .pointer(ENABLED_PTR)
+            .and_then(|enabled| bool::deserialize(enabled).ok())
+            .unwrap_or(true),
+        None => default_enabled,
+    };
+    let path = endpoint.and_then(|endpoint| match endpoint {
+        Value::Object(_) => endpoint.pointer(PATH_PTR),
+        _ => Some(endpoint),
+    });
+
+    let Some(path) = path else {
+        return Endpoint::new_unchecked(default).with_enabled(enabled);
+    };
+
+    Endpoint::deserialize(path)
+        .unwrap_or_else(|_| {
+            let rejected = path
+                .as_str()
+                .map_or_else(|| path.to_string(), ToOwned::to_owned);
+
+            Endpoint {
+                rejected: Some(rejected),
+                ..Endpoint::new_unchecked(default)
+            }
+        })
+        .with_enabled(enabled)
+}
+
 #[derive(Debug, Clone)]
 /// This is simply a wrapper over [`String`] but it checks if [`String`] starts with '/' symbol.
-pub struct Endpoint(String);
+/// Also keeps flag whether endpoint should be served.
+pub struct Endpoint {
+    path: String,
+    enabled: bool,
+    rejected: Option<String>,
+}
 
 impl Endpoint {
-    /// Creates new [`Endpoint`].
+    /// Creates new enabled [`Endpoint`].
     /// Returns error if str does not start with '/' symbol.
     pub fn new(path: &str) -> Result<Self, &'static str> {
         if path.starts_with('/') {
-            Ok(Endpoint(path.to_owned()))
+            Ok(Endpoint::new_unchecked(path))
         } else {
             Err("Endpoint must start with a `/`")
         }
     }
+
+    /// Returns [`true`] if endpoint should be served.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Enables or disables endpoint.
+    #[must_use]
+    pub fn with_enabled(self, enabled: bool) -> Self {
+        Self { enabled, ..self }
+    }
+
+    fn new_unchecked(path: &str) -> Self {
+        Endpoint {
+            path: path.to_owned(),
+            enabled: true,
+            rejected: None,
+        }
+    }
 }
 
 impl<'de> Deserialize<'de> for Endpoint {
@@ -163,6 +317,6 @@ impl<'de> Deserialize<'de> for Endpoint {
 
 impl AsRef<str> for Endpoint {
     fn as_ref(&self) -> &str {
-        self.0.as_ref()
+        self.path.as_ref()
     }
 }
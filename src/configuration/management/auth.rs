@@ -0,0 +1,341 @@
+use crate::extensions::DeserializeExt;
+use crate::observability::SANITIZED_VALUE;
+use crate::{ConfigSchema, Validate, ValidationErrors};
+use serde::de::Error;
+use serde::{Deserialize, Deserializer};
+use serde_json::{json, Value};
+use std::fmt::{Debug, Formatter};
+use std::net::IpAddr;
+use std::str::FromStr;
+
+const BEARER_TOKEN_PTR: &str = "/bearer/token";
+const BEARER_PATH_PTR: &str = "/bearer/path";
+const BASIC_USERNAME_PTR: &str = "/basic/username";
+const BASIC_PASSWORD_PTR: &str = "/basic/password";
+const BASIC_PATH_PTR: &str = "/basic/path";
+const ALLOW_PTR: &str = "/allow";
+const EXEMPT_PROBES_PTR: &str = "/exempt/probes";
+const EXEMPT_METRICS_PTR: &str = "/exempt/metrics";
+
+/// Authentication and IP allow-listing for management endpoints.\
+/// By default everything is disabled and management endpoints are anonymous.\
+/// Credentials might be given as plain values (usually through environment variables) or read from files:
+/// ```toml
+/// [management.auth.bearer]
+/// token = "secret"              # or: path = "/run/secrets/management_token"
+///
+/// [management.auth.basic]
+/// username = "admin"
+/// password = "secret"           # or: path = "/run/secrets/management_basic" containing "username:password"
+///
+/// [management.auth]
+/// allow = "10.0.0.0/8,127.0.0.1" # comma separated list of CIDRs or IPs
+///
+/// [management.auth.exempt]
+/// probes = true                 # health, live and ready endpoints skip auth checks
+/// metrics = false               # metrics endpoint skips auth checks
+/// ```
+/// If both bearer and basic credentials are configured request is authorised if any of them matches.
+#[derive(Clone, Default)]
+pub struct ManagementAuth {
+    /// Expected `Authorization: Bearer` token.
+    pub bearer: Option<String>,
+    /// Expected `Authorization: Basic` credentials.
+    pub basic: Option<BasicCredentials>,
+    /// If set, only requests from these networks are accepted.
+    pub allow: Option<Vec<IpNetwork>>,
+    /// Endpoints which skip authentication and allow-list checks.
+    pub exempt: AuthExempt,
+}
+
+impl ManagementAuth {
+    /// Returns [`true`] if any of credentials or allow-list is configured.
+    pub fn is_enabled(&self) -> bool {
+        self.bearer.is_some() || self.basic.is_some() || self.allow.is_some()
+    }
+
+    /// Returns [`true`] if request must carry `Authorization` header.
+    pub fn requires_credentials(&self) -> bool {
+        self.bearer.is_some() || self.basic.is_some()
+    }
+
+    /// Returns [`true`] if given address is allowed to access management endpoints.
+    pub fn is_allowed(&self, ip: IpAddr) -> bool {
+        match &self.allow {
+            Some(allow) => allow.iter().any(|network| network.contains(ip)),
+            None => true,
+        }
+    }
+}
+
+impl Debug for ManagementAuth {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ManagementAuth")
+            .field("bearer", &self.bearer.as_ref().map(|_| SANITIZED_VALUE))
+            .field("basic", &self.basic)
+            .field("allow", &self.allow)
+            .field("exempt", &self.exempt)
+            .finish()
+    }
+}
+
+impl Validate for ManagementAuth {
+    fn validate(&self, errors: &mut ValidationErrors) {
+        if matches!(&self.allow, Some(allow) if allow.is_empty()) {
+            errors.add(
+                ALLOW_PTR,
+                "allow-list is empty and would reject every request, remove `allow` to accept any address",
+            );
+        }
+    }
+}
+
+impl ConfigSchema for ManagementAuth {
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "description": "authentication and IP allow-list for management endpoints, disabled by default",
+            "properties": {
+                "allow": {
+                    "type": "string",
+                    "description": "comma separated list of CIDRs or IPs allowed to access management endpoints",
+                    "examples": ["10.0.0.0/8,127.0.0.1"]
+                },
+                "bearer": {
+                    "type": "object",
+                    "description": "expected `Authorization: Bearer` token",
+                    "properties": {
+                        "token": { "type": "string", "description": "token value", "examples": ["secret"] },
+                        "path": { "type": "string", "description": "file to read token from", "examples": ["/run/secrets/management_token"] }
+                    }
+                },
+                "basic": {
+                    "type": "object",
+                    "description": "expected `Authorization: Basic` credentials",
+                    "properties": {
+                        "username": { "type": "string", "examples": ["admin"] },
+                        "password": { "type": "string", "examples": ["secret"] },
+                        "path": { "type": "string", "description": "file containing `username:password`", "examples": ["/run/secrets/management_basic"] }
+                    }
+                },
+                "exempt": {
+                    "type": "object",
+                    "description": "endpoints which skip auth and allow-list checks",
+                    "properties": {
+                        "probes": { "type": "boolean", "description": "health, live and ready endpoints", "default": false },
+                        "metrics": { "type": "boolean", "description": "metrics endpoint", "default": false }
+                    }
+                }
+            }
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for ManagementAuth {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let config = Value::deserialize(deserializer)?;
+
+        let bearer = match config.pointer(BEARER_TOKEN_PTR) {
+            Some(token) => Some(String::deserialize(token).map_err(D::Error::custom)?),
+            None => config
+                .pointer(BEARER_PATH_PTR)
+                .map(String::deserialize)
+                .transpose()
+                .map_err(D::Error::custom)?
+                .map(|path| read_secret_file(&path))
+                .transpose()
+                .map_err(D::Error::custom)?,
+        };
+
+        let basic = match config.pointer(BASIC_USERNAME_PTR) {
+            Some(username) => Some(BasicCredentials {
+                username: String::deserialize(username).map_err(D::Error::custom)?,
+                password: config.pointer_and_deserialize(BASIC_PASSWORD_PTR)?,
+            }),
+            None => config
+                .pointer(BASIC_PATH_PTR)
+                .map(String::deserialize)
+                .transpose()
+                .map_err(D::Error::custom)?
+                .map(|path| {
+                    read_secret_file(&path)?
+                        .parse::<BasicCredentials>()
+                        .map_err(|err| format!("Invalid basic credentials in `{path}`: {err}"))
+                })
+                .transpose()
+                .map_err(D::Error::custom)?,
+        };
+
+        let allow = config
+            .pointer(ALLOW_PTR)
+            .map(parse_allow_list)
+            .transpose()
+            .map_err(D::Error::custom)?;
+
+        let exempt = AuthExempt {
+            probes: config
+                .pointer(EXEMPT_PROBES_PTR)
+                .map(bool::deserialize)
+                .transpose()
+                .map_err(D::Error::custom)?
+                .unwrap_or_default(),
+            metrics: config
+                .pointer(EXEMPT_METRICS_PTR)
+                .map(bool::deserialize)
+                .transpose()
+                .map_err(D::Error::custom)?
+                .unwrap_or_default(),
+        };
+
+        Ok(ManagementAuth {
+            bearer,
+            basic,
+            allow,
+            exempt,
+        })
+    }
+}
+
+/// Endpoints which might skip authentication and allow-list checks.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AuthExempt {
+    /// health, live and ready endpoints
+    pub probes: bool,
+    /// metrics endpoint
+    pub metrics: bool,
+}
+
+/// Username and password for `Authorization: Basic` header.
+#[derive(Clone, PartialEq, Eq)]
+pub struct BasicCredentials {
+    /// username
+    pub username: String,
+    /// password
+    pub password: String,
+}
+
+impl Debug for BasicCredentials {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BasicCredentials")
+            .field("username", &self.username)
+            .field("password", &SANITIZED_VALUE)
+            .finish()
+    }
+}
+
+impl FromStr for BasicCredentials {
+    type Err = &'static str;
+
+    /// Parses credentials in `username:password` format.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (username, password) = s
+            .split_once(':')
+            .ok_or("Expected credentials in `username:password` format")?;
+
+        Ok(BasicCredentials {
+            username: username.to_owned(),
+            password: password.to_owned(),
+        })
+    }
+}
+
+/// IP network in CIDR notation, e.g. `10.0.0.0/8` or `::1/128`.\
+/// IP address without prefix length is treated as a network with a single address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IpNetwork {
+    addr: IpAddr,
+    prefix_len: u8,
+}
+
+impl IpNetwork {
+    /// Creates new [`IpNetwork`].
+    /// Returns error if prefix length exceeds address length.
+    pub fn new(addr: IpAddr, prefix_len: u8) -> Result<Self, &'static str> {
+        let max_len = match addr {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        };
+
+        if prefix_len > max_len {
+            Err("Prefix length exceeds address length")
+        } else {
+            Ok(IpNetwork { addr, prefix_len })
+        }
+    }
+
+    /// Returns [`true`] if network contains given address.
+    /// IPv4-mapped IPv6 addresses are matched against IPv4 networks.
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        let ip = match ip {
+            IpAddr::V6(v6) => v6.to_ipv4_mapped().map_or(ip, IpAddr::V4),
+            IpAddr::V4(_) => ip,
+        };
+
+        match (self.addr, ip) {
+            (IpAddr::V4(network), IpAddr::V4(ip)) => {
+                let mask = u32::MAX
+                    .checked_shl(32 - u32::from(self.prefix_len))
+                    .unwrap_or(0);
+                u32::from(network) & mask == u32::from(ip) & mask
+            }
+            (IpAddr::V6(network), IpAddr::V6(ip)) => {
+                let mask = u128::MAX
+                    .checked_shl(128 - u32::from(self.prefix_len))
+                    .unwrap_or(0);
+                u128::from(network) & mask == u128::from(ip) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+impl FromStr for IpNetwork {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        match s.split_once('/') {
+            Some((addr, prefix_len)) => {
+                let addr = addr.parse().map_err(|_| "Invalid IP address")?;
+                let prefix_len = prefix_len.parse().map_err(|_| "Invalid prefix length")?;
+                IpNetwork::new(addr, prefix_len)
+            }
+            None => {
+                let addr: IpAddr = s.parse().map_err(|_| "Invalid IP address")?;
+                let prefix_len = if addr.is_ipv4() { 32 } else { 128 };
+                IpNetwork::new(addr, prefix_len)
+            }
+        }
+    }
+}
+
+fn parse_allow_list(value: &Value) -> Result<Vec<IpNetwork>, String> {
+    let networks: Vec<String> = match value {
+        Value::String(str) => str
+            .split(',')
+            .map(str::trim)
+            .filter(|str| !str.is_empty())
+            .map(ToOwned::to_owned)
+            .collect(),
+        value => Vec::<String>::deserialize(value).map_err(|err| err.to_string())?,
+    };
+
+    networks
+        .iter()
+        .map(|network| {
+            network
+                .parse::<IpNetwork>()
+                .map_err(|err| format!("Invalid network `{network}` in allow-list: {err}"))
+        })
+        .collect()
+}
+
+fn read_secret_file(path: &str) -> Result<String, String> {
+    std::fs::read_to_string(path)
+        .map(|content| content.trim().to_owned())
+        .map_err(|err| format!("Cant read secret file `{path}`: `{err}`."))
+}
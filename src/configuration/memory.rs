@@ -0,0 +1,78 @@
+use crate::configuration::args::parse_value;
+use config::{ConfigError, Map, Source, Value};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Reads top-level keys of JSON object, see [`crate::ConfigSource::Value`].
+#[derive(Debug, Clone)]
+pub(crate) struct JsonValue {
+    value: serde_json::Value,
+}
+
+impl JsonValue {
+    pub(crate) fn new(value: serde_json::Value) -> Self {
+        Self { value }
+    }
+}
+
+impl Source for JsonValue {
+    fn clone_into_box(&self) -> Box<dyn Source + Send + Sync> {
+        Box::new(self.clone())
+    }
+
+    fn collect(&self) -> Result<Map<String, Value>, ConfigError> {
+        let serde_json::Value::Object(object) = &self.value else {
+            return Err(ConfigError::Message(format!(
+                "Configuration value must be an object, got `{}`.",
+                self.value
+            )));
+        };
+
+        object
+            .iter()
+            .map(|(key, value)| {
+                Value::deserialize(value)
+                    .map(|value| (key.clone(), value))
+                    .map_err(|err| ConfigError::Message(err.to_string()))
+            })
+            .collect()
+    }
+}
+
+/// Sets values by keys with nested keys separated with `.`, see [`crate::ConfigSource::Map`].
+#[derive(Debug, Clone)]
+pub(crate) struct Values {
+    values: Map<String, Value>,
+}
+
+impl Values {
+    pub(crate) fn new(values: Map<String, Value>) -> Self {
+        Self { values }
+    }
+
+    /// String values are parsed the same way as environment variables.
+    pub(crate) fn from_strings(values: HashMap<String, String>) -> Self {
+        let values = values
+            .into_iter()
+            .map(|(key, value)| (key.to_lowercase(), Value::new(None, parse_value(value))))
+            .collect();
+
+        Self { values }
+    }
+
+    pub(crate) fn single(key: &str, value: Value) -> Self {
+        Self {
+            values: Map::from([(key.to_owned(), value)]),
+        }
+    }
+}
+
+impl Source for Values {
+    fn clone_into_box(&self) -> Box<dyn Source + Send + Sync> {
+        Box::new(self.clone())
+    }
+
+    fn collect(&self) -> Result<Map<String, Value>, ConfigError> {
+        Ok(self.values.clone())
+    }
+}
@@ -1,13 +1,26 @@
+use crate::configuration::log_output::{
+    syslog_schema, LogFileConfig, LogFormat, LogOutput, LogSinkConfig,
+};
+use crate::configuration::units::{DurationValue, SizeValue};
 use crate::extensions::DeserializeExt;
-use crate::observability::HeadersFilter;
+use crate::observability::{HeadersFilter, LogSchema, LogSpans};
+use crate::{ConfigSchema, Validate, ValidationErrors};
 use serde::de::Error;
 use serde::{Deserialize, Deserializer};
-use serde_json::{from_value, Value};
+use serde_json::{from_value, json, Value};
+use std::str::FromStr;
+use tracing_subscriber::EnvFilter;
 
 #[cfg(feature = "tokio-metrics")]
 const SERVER_METRICS_UPDATE_INTERVAL_PTR: &str = "/server/metrics/update_interval";
+const SERVER_METRICS_EXEMPLARS_PTR: &str = "/server/metrics/exemplars";
+const SERVER_METRICS_CALLBACK_TIMEOUT_PTR: &str = "/server/metrics/callback/timeout";
+const LOG_PTR: &str = "/log";
 const LOG_LEVEL_PTR: &str = "/log/level";
 const LOG_MSG_LENGTH_PTR: &str = "/log/msg/length";
+const LOG_SINKS_PTR: &str = "/log/sinks";
+const LOG_FORMAT_PTR: &str = "/log/format";
+const LOG_SPANS_PTR: &str = "/log/spans";
 const BUFFERED_LINES_LIMIT_PTR: &str = "/buffered/lines/limit";
 const TRACE_LEVEL_PTR: &str = "/trace/level";
 const SERVICE_NAME_PTR: &str = "/service/name";
@@ -23,6 +36,16 @@ pub struct ObservabilityConfig {
     pub log_level: String,
     /// Maximum message field length, if set: message field will be cut if len() exceed this limit
     pub msg_length: Option<usize>,
+    /// where logs are written, see [`LogOutput`]
+    pub log_output: LogOutput,
+    /// format of log lines, see [`LogFormat`]
+    pub log_format: LogFormat,
+    /// keys and level values of log lines, see [`LogSchema`]
+    pub log_schema: LogSchema,
+    /// span fields written with each event, see [`LogSpans`]
+    pub log_spans: LogSpans,
+    /// additional log sinks, see [`LogSinkConfig`]
+    pub log_sinks: Vec<LogSinkConfig>,
     /// Sets limit for [`tracing_appender::non_blocking::NonBlocking`]
     pub buffered_lines_limit: Option<usize>,
     /// trace level read to string and later parsed into EnvFilter
@@ -36,6 +59,10 @@ pub struct ObservabilityConfig {
     /// Tokio metrics update interval
     #[cfg(feature = "tokio-metrics")]
     pub metrics_update_interval: std::time::Duration,
+    /// if set metrics carry exemplars with `trace_id` in OpenMetrics format, see [`crate::observability::init_metrics_with_exemplars`]
+    pub metrics_exemplars: bool,
+    /// Timeout for async metrics callbacks, see [`crate::observability::run_metrics_callbacks`]
+    pub metrics_callback_timeout: Option<std::time::Duration>,
     /// configures [`tracing_opentelemetry::layer`] endpoint for sending traces.
     pub traces_endpoint: Option<String>,
     /// initialize [`crate::observability::HEADERS_FILTER`] static variable in [`crate::bootstrap()`] or [`crate::observability::init_tracing()`] fn.
@@ -61,14 +88,46 @@ impl<'de> Deserialize<'de> for ObservabilityConfig {
             .transpose()
             .map_err(D::Error::custom)?;
         #[cfg(feature = "tokio-metrics")]
-        let metrics_update_interval =
-            config.pointer_and_deserialize::<u64, D::Error>(SERVER_METRICS_UPDATE_INTERVAL_PTR)?;
+        let DurationValue(metrics_update_interval) =
+            config.pointer_and_deserialize(SERVER_METRICS_UPDATE_INTERVAL_PTR)?;
         let msg_length = config
-            .pointer_and_deserialize::<_, D::Error>(LOG_MSG_LENGTH_PTR)
-            .ok();
+            .pointer(LOG_MSG_LENGTH_PTR)
+            .map(SizeValue::deserialize)
+            .transpose()
+            .map_err(D::Error::custom)?
+            .map(|SizeValue(length)| length);
+        let log_output = config.pointer_and_deserialize(LOG_PTR)?;
+        let log_format = config
+            .pointer(LOG_FORMAT_PTR)
+            .map(LogFormat::deserialize)
+            .transpose()
+            .map_err(D::Error::custom)?
+            .unwrap_or_default();
+        let log_schema = config.pointer_and_deserialize(LOG_PTR)?;
+        let log_spans = config
+            .pointer(LOG_SPANS_PTR)
+            .map(LogSpans::deserialize)
+            .transpose()
+            .map_err(D::Error::custom)?
+            .unwrap_or_default();
+        let log_sinks = config
+            .pointer(LOG_SINKS_PTR)
+            .map(Vec::<LogSinkConfig>::deserialize)
+            .transpose()
+            .map_err(D::Error::custom)?
+            .unwrap_or_default();
         let buffered_lines_limit = config
             .pointer_and_deserialize::<_, D::Error>(BUFFERED_LINES_LIMIT_PTR)
             .ok();
+        let metrics_exemplars = config
+            .pointer_and_deserialize::<_, D::Error>(SERVER_METRICS_EXEMPLARS_PTR)
+            .unwrap_or_default();
+        let metrics_callback_timeout = config
+            .pointer(SERVER_METRICS_CALLBACK_TIMEOUT_PTR)
+            .map(DurationValue::deserialize)
+            .transpose()
+            .map_err(D::Error::custom)?
+            .map(|DurationValue(timeout)| timeout);
         let headers_filter: Option<HeadersFilter> = config
             .pointer_and_deserialize::<_, D::Error>(HEADERS_PTR)
             .ok();
@@ -76,6 +135,11 @@ impl<'de> Deserialize<'de> for ObservabilityConfig {
         Ok(ObservabilityConfig {
             log_level,
             msg_length,
+            log_output,
+            log_format,
+            log_schema,
+            log_spans,
+            log_sinks,
             version,
             trace_level,
             service_name,
@@ -83,8 +147,178 @@ impl<'de> Deserialize<'de> for ObservabilityConfig {
             traces_endpoint,
             buffered_lines_limit,
             headers_filter,
+            metrics_exemplars,
+            metrics_callback_timeout,
             #[cfg(feature = "tokio-metrics")]
-            metrics_update_interval: std::time::Duration::from_millis(metrics_update_interval),
+            metrics_update_interval,
         })
     }
 }
+
+impl Validate for ObservabilityConfig {
+    fn validate(&self, errors: &mut ValidationErrors) {
+        for (pointer, level) in [
+            (LOG_LEVEL_PTR, &self.log_level),
+            (TRACE_LEVEL_PTR, &self.trace_level),
+        ] {
+            if let Err(err) = EnvFilter::from_str(level) {
+                errors.add(
+                    pointer,
+                    format!("invalid filter directive `{level}`: {err}"),
+                );
+            }
+        }
+
+        for (index, sink) in self.log_sinks.iter().enumerate() {
+            if let Err(err) = EnvFilter::from_str(&sink.level) {
+                errors.add(
+                    &format!("{LOG_SINKS_PTR}/{index}/level"),
+                    format!("invalid filter directive `{}`: {err}", sink.level),
+                );
+            }
+            if self
+                .log_sinks
+                .iter()
+                .take(index)
+                .any(|other| other.name == sink.name)
+            {
+                errors.add(
+                    &format!("{LOG_SINKS_PTR}/{index}/name"),
+                    format!("duplicate log sink name `{}`", sink.name),
+                );
+            }
+        }
+    }
+}
+
+impl ConfigSchema for ObservabilityConfig {
+    fn schema() -> Value {
+        let mut schema = json!({
+            "type": "object",
+            "properties": {
+                "log": {
+                    "type": "object",
+                    "properties": {
+                        "level": { "type": "string", "description": "log filter directives, e.g. `info,hyper=warn`" },
+                        "output": {
+                            "type": "string",
+                            "enum": ["stdout", "stderr", "file", "syslog"],
+                            "description": "where logs are written, `file` is configured with `log.file` and `syslog` with `log.syslog`"
+                        },
+                        "format": {
+                            "type": "string",
+                            "enum": ["json", "pretty", "compact"],
+                            "description": "`json` lines or colored `pretty` and `compact` text for local development"
+                        },
+                        "file": LogFileConfig::schema(),
+                        "syslog": syslog_schema(),
+                        "spans": LogSpans::schema(),
+                        "sinks": {
+                            "type": "array",
+                            "description": "additional sinks with their own level and output",
+                            "items": LogSinkConfig::schema(),
+                            "examples": [[{ "name": "debug_file", "level": "debug", "output": "file", "file": { "path": "/var/log/debug.log" } }]]
+                        },
+                        "msg": {
+                            "type": "object",
+                            "properties": {
+                                "length": { "type": ["integer", "string"], "description": "maximum length of message field, e.g. `8KiB`, bare number is read as bytes" }
+                            }
+                        }
+                    }
+                },
+                "buffered": {
+                    "type": "object",
+                    "properties": {
+                        "lines": {
+                            "type": "object",
+                            "properties": {
+                                "limit": {
+                                    "type": "integer",
+                                    "minimum": 0,
+                                    "description": "maximum number of buffered log lines before new lines are dropped",
+                                    "examples": [128000]
+                                }
+                            }
+                        }
+                    }
+                },
+                "trace": {
+                    "type": "object",
+                    "properties": {
+                        "level": { "type": "string", "description": "trace filter directives, e.g. `info`" }
+                    }
+                },
+                "service": {
+                    "type": "object",
+                    "properties": {
+                        "name": { "type": "string", "description": "service name used in logs and traces" }
+                    }
+                },
+                "component": {
+                    "type": "object",
+                    "properties": {
+                        "name": { "type": "string", "description": "component name used in logs, traces and version endpoint" },
+                        "version": { "type": "string", "description": "component version returned by version endpoint" }
+                    }
+                },
+                "exporter": {
+                    "type": "object",
+                    "properties": {
+                        "otlp": {
+                            "type": "object",
+                            "properties": {
+                                "traces": {
+                                    "type": "object",
+                                    "properties": {
+                                        "endpoint": {
+                                            "type": "string",
+                                            "description": "OTLP gRPC endpoint to export traces to, traces are not exported if unset",
+                                            "examples": ["http://localhost:4317"]
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                },
+                "headers": {
+                    "type": "object",
+                    "description": "comma separated header names or `*` for all headers",
+                    "properties": {
+                        "include": { "type": "string", "description": "headers to log" },
+                        "exclude": { "type": "string", "description": "headers not to log", "examples": ["authorization"] },
+                        "sanitize": { "type": "string", "description": "headers to log with hidden value", "examples": ["password,client_id"] }
+                    }
+                },
+                "server": {
+                    "type": "object",
+                    "properties": {
+                        "metrics": {
+                            "type": "object",
+                            "properties": {
+                                "update_interval": { "type": ["integer", "string"], "description": "tokio metrics update interval, e.g. `1s`, bare number is read as milliseconds" },
+                                "exemplars": { "type": "boolean", "description": "attach trace_id exemplars to OpenMetrics output" },
+                                "callback": {
+                                    "type": "object",
+                                    "properties": {
+                                        "timeout": { "type": ["integer", "string"], "description": "timeout of async metrics callbacks, e.g. `1s`, bare number is read as milliseconds" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        });
+
+        if let (Some(Value::Object(log)), Some(Value::Object(log_schema))) = (
+            schema.pointer_mut("/properties/log/properties"),
+            LogSchema::schema().pointer("/properties"),
+        ) {
+            log.extend(log_schema.clone());
+        }
+
+        schema
+    }
+}
@@ -0,0 +1,124 @@
+use config::{Map, Value, ValueKind};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+/// Sources of a single configuration key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyProvenance {
+    /// source which supplied resolved value
+    pub source: String,
+    /// sources which set the key before and were overridden, in order they were added
+    pub overridden: Vec<String>,
+}
+
+/// Records which source supplied each resolved configuration key, see [`crate::AppConfig::explain`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Provenance {
+    keys: BTreeMap<String, KeyProvenance>,
+}
+
+impl Provenance {
+    /// Collects keys of values read from each source in order they were added to [`crate::AppConfigBuilder`].
+    pub(crate) fn collect(sources: &[(String, Map<String, Value>)]) -> Self {
+        let mut set_by = BTreeMap::<String, Vec<String>>::new();
+
+        for (name, values) in sources {
+            let mut keys = Vec::new();
+            for (key, value) in values {
+                flatten(key.to_lowercase(), value, &mut keys);
+            }
+
+            for key in keys {
+                set_by.entry(key).or_default().push(name.clone());
+            }
+        }
+
+        let keys = set_by
+            .into_iter()
+            .filter_map(|(key, mut sources)| {
+                let source = sources.pop()?;
+                Some((
+                    key,
+                    KeyProvenance {
+                        source,
+                        overridden: sources,
+                    },
+                ))
+            })
+            .collect();
+
+        Self { keys }
+    }
+
+    /// Returns sources of key, nested keys are separated with `.`, e.g. `log.level`.
+    pub fn get(&self, key: &str) -> Option<&KeyProvenance> {
+        self.keys.get(key)
+    }
+
+    /// Returns keys which resolved values were supplied by one of given sources.
+    pub(crate) fn keys_set_by<'a>(
+        &'a self,
+        sources: &'a [String],
+    ) -> impl Iterator<Item = &'a str> {
+        self.keys
+            .iter()
+            .filter(|(_, key)| sources.contains(&key.source))
+            .map(|(key, _)| key.as_str())
+    }
+
+    /// Returns source which supplied key, a nested key or, for array items, the closest parent key.
+    pub(crate) fn source_of(&self, key: &str) -> Option<&str> {
+        let mut key = key;
+
+        loop {
+            let nested = format!("{key}.");
+            let found = self.keys.get(key).or_else(|| {
+                self.keys
+                    .range(nested.clone()..)
+                    .next()
+                    .filter(|(found, _)| found.starts_with(&nested))
+                    .map(|(_, sources)| sources)
+            });
+
+            if let Some(found) = found {
+                return Some(&found.source);
+            }
+
+            key = key.rsplit_once('.')?.0;
+        }
+    }
+
+    /// Iterates over all keys in alphabetical order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &KeyProvenance)> {
+        self.keys
+            .iter()
+            .map(|(key, sources)| (key.as_str(), sources))
+    }
+}
+
+impl Display for Provenance {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (key, KeyProvenance { source, overridden }) in &self.keys {
+            write!(f, "{key}: {source}")?;
+
+            if !overridden.is_empty() {
+                write!(f, " (overrides {})", overridden.join(", "))?;
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn flatten(key: String, value: &Value, keys: &mut Vec<String>) {
+    match &value.kind {
+        ValueKind::Table(table) if !table.is_empty() => {
+            for (nested, value) in table {
+                flatten(format!("{key}.{}", nested.to_lowercase()), value, keys);
+            }
+        }
+        _ => keys.push(key),
+    }
+}
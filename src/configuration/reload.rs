@@ -0,0 +1,279 @@
+use crate::configuration::application::DEFAULT_SEPARATOR;
+use crate::configuration::directory::conf_files;
+use crate::configuration::secrets::{secret_files, EnvFiles};
+use crate::configuration::{AppConfig, ConfigSource, Empty, ObservabilityConfig};
+use crate::error::{Error, Result};
+use crate::observability::{
+    reload_headers_filter, LOG_LAYER_HANDLE, LOG_SINK_HANDLES, OTLP_LAYER_HANDLE,
+};
+use crate::{Validate, ValidationErrors};
+use serde::de::DeserializeOwned;
+use std::fmt::Debug;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tokio::sync::watch;
+use tokio::task::spawn_blocking;
+use tracing::{info, warn};
+use tracing_subscriber::EnvFilter;
+
+const FILE_EXTENSIONS: [&str; 7] = ["toml", "json", "yaml", "yml", "ini", "ron", "json5"];
+
+/// Handle to [`AppConfig`] which is rebuilt whenever one of [`ConfigSource::File`], [`ConfigSource::Profiled`], [`ConfigSource::SecretsDir`], [`ConfigSource::EnvPrefix`] `_FILE` variables, [`ConfigSource::Dir`] or [`ConfigSource::DotEnv`] sources changes.\
+/// Created with [`AppConfig::watch`] or [`AppConfig::watch_validated`]. On every successful reload log level, log sink levels, trace level, headers filter and tokio metrics update interval are applied automatically,
+/// configuration which fails to load or [`crate::Validate`] is rejected and logged while previous one stays in use.\
+/// Watcher keeps running while at least one [`ConfigHandle`] is alive.
+#[derive(Debug, Clone)]
+pub struct ConfigHandle<ConfigExt = Empty> {
+    receiver: watch::Receiver<Arc<AppConfig<ConfigExt>>>,
+}
+
+impl<ConfigExt> ConfigHandle<ConfigExt> {
+    /// Returns current configuration.
+    pub fn get(&self) -> Arc<AppConfig<ConfigExt>> {
+        self.receiver.borrow().clone()
+    }
+
+    /// Waits for the next reload and returns new configuration.\
+    /// Returns [`None`] if watcher has stopped.
+    pub async fn changed(&mut self) -> Option<Arc<AppConfig<ConfigExt>>> {
+        self.receiver.changed().await.ok()?;
+        Some(self.receiver.borrow_and_update().clone())
+    }
+
+    /// Returns [`watch::Receiver`] notified on each reload.
+    pub fn subscribe(&self) -> watch::Receiver<Arc<AppConfig<ConfigExt>>> {
+        self.receiver.clone()
+    }
+}
+
+impl<ConfigExt> AppConfig<ConfigExt> {
+    /// Starts polling [`ConfigSource::File`], [`ConfigSource::Profiled`], [`ConfigSource::SecretsDir`], [`ConfigSource::EnvPrefix`] `_FILE` variables, [`ConfigSource::Dir`] and [`ConfigSource::DotEnv`] sources every `interval` and returns [`ConfigHandle`] with this configuration as current one.\
+    /// `sources` must be the same this configuration was loaded from, see [`AppConfig::load_from`].
+    /// Handle starts with a clone of this configuration, [`AppConfig::worker_guard`] and [`AppConfig::log_guard`] stay here.\
+    /// Files are checked and configuration is rebuilt on blocking threads, see [`tokio::task::spawn_blocking`].
+    /// ```no_run
+    /// use fregate::{bootstrap, AppConfig, ConfigSource};
+    /// use std::time::Duration;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let sources = vec![ConfigSource::File("./app.yaml")];
+    ///     let config: AppConfig = bootstrap(sources.clone()).unwrap();
+    ///
+    ///     let mut handle = config.watch(sources, Duration::from_secs(5));
+    ///
+    ///     while let Some(config) = handle.changed().await {
+    ///         println!("reloaded: {:?}", config.private);
+    ///     }
+    /// }
+    /// ```
+    pub fn watch(
+        &self,
+        sources: Vec<ConfigSource<'static>>,
+        interval: Duration,
+    ) -> ConfigHandle<ConfigExt>
+    where
+        ConfigExt: Clone + Debug + DeserializeOwned + Send + Sync + 'static,
+    {
+        self.watch_with(sources, interval, None)
+    }
+
+    /// Same as [`AppConfig::watch`] and validates [`AppConfig::private`] of each reloaded configuration with its [`Validate`] implementation,
+    /// use it for configuration loaded with [`AppConfig::load_validated_from`] or [`crate::bootstrap_validated`].
+    pub fn watch_validated(
+        &self,
+        sources: Vec<ConfigSource<'static>>,
+        interval: Duration,
+    ) -> ConfigHandle<ConfigExt>
+    where
+        ConfigExt: Clone + Debug + DeserializeOwned + Validate + Send + Sync + 'static,
+    {
+        self.watch_with(sources, interval, Some(ConfigExt::validate))
+    }
+
+    fn watch_with(
+        &self,
+        sources: Vec<ConfigSource<'static>>,
+        interval: Duration,
+        validate_private: Option<fn(&ConfigExt, &mut ValidationErrors)>,
+    ) -> ConfigHandle<ConfigExt>
+    where
+        ConfigExt: Clone + Debug + DeserializeOwned + Send + Sync + 'static,
+    {
+        let sources = Arc::new(sources);
+        let mut modified = files_modified(&sources);
+        let (sender, receiver) = watch::channel(Arc::new(self.clone()));
+
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = sender.closed() => break,
+                    _ = tokio::time::sleep(interval) => {}
+                }
+
+                let files = sources.clone();
+                let Ok(current) = spawn_blocking(move || files_modified(&files)).await else {
+                    continue;
+                };
+                if current == modified {
+                    continue;
+                }
+                modified = current;
+
+                let reload_sources = sources.clone();
+                let reloaded = spawn_blocking(move || reload(&reload_sources, validate_private))
+                    .await
+                    .unwrap_or_else(|err| Err(Error::CustomError(err.to_string())));
+
+                match reloaded {
+                    Ok(config) => {
+                        apply_observability(
+                            &sender.borrow().observability_cfg,
+                            &config.observability_cfg,
+                        );
+                        info!(target: "config", "Configuration reloaded: `{config:?}`.");
+                        sender.send_replace(Arc::new(config));
+                    }
+                    Err(err) => {
+                        warn!(target: "config", "Rejected configuration reload: `{err}`.");
+                    }
+                }
+            }
+        });
+
+        ConfigHandle { receiver }
+    }
+}
+
+fn reload<ConfigExt>(
+    sources: &[ConfigSource<'static>],
+    validate_private: Option<fn(&ConfigExt, &mut ValidationErrors)>,
+) -> Result<AppConfig<ConfigExt>>
+where
+    ConfigExt: Debug + DeserializeOwned,
+{
+    let mut builder = AppConfig::sources_builder(sources.iter().cloned())?;
+    builder.validate_private = validate_private;
+    builder.build()
+}
+
+/// Modification times and sizes of files which might be read for each [`ConfigSource::File`], [`ConfigSource::Profiled`], [`ConfigSource::SecretsDir`], [`ConfigSource::EnvPrefix`] `_FILE` variables, [`ConfigSource::Dir`] and [`ConfigSource::DotEnv`], path might be given without extension.\
+/// Size is compared as well since modification time might be too coarse to tell apart writes done within the same second.
+fn files_modified(sources: &[ConfigSource<'_>]) -> Vec<Option<(SystemTime, u64)>> {
+    sources
+        .iter()
+        .flat_map(|source| match source {
+            ConfigSource::File(path) => {
+                let path = Path::new(path);
+                std::iter::once(path.to_path_buf())
+                    .chain(
+                        FILE_EXTENSIONS
+                            .iter()
+                            .map(|extension| path.with_extension(extension)),
+                    )
+                    .collect()
+            }
+            ConfigSource::Profiled(path) => profiled_files(Path::new(path)),
+            ConfigSource::SecretsDir(path) => secret_files(Path::new(path)).unwrap_or_default(),
+            ConfigSource::DotEnv(path) => vec![Path::new(path).to_path_buf()],
+            ConfigSource::EnvPrefix(prefix) => EnvFiles::new(prefix, DEFAULT_SEPARATOR).files(),
+            ConfigSource::Dir(path) => {
+                let path = Path::new(path);
+                [path.to_path_buf(), path.join("..data")]
+                    .into_iter()
+                    .chain(conf_files(path).unwrap_or_default())
+                    .collect()
+            }
+            _ => Vec::new(),
+        })
+        .map(|path| {
+            path.metadata()
+                .and_then(|meta| Ok((meta.modified()?, meta.len())))
+                .ok()
+        })
+        .collect()
+}
+
+/// Base file and all profile files next to it.
+fn profiled_files(path: &Path) -> Vec<PathBuf> {
+    let Some(name) = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+    else {
+        return Vec::new();
+    };
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+
+    let mut files = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|file| {
+                    file.file_name()
+                        .is_some_and(|file| file.to_string_lossy().starts_with(&name))
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    files.sort();
+    files
+}
+
+fn apply_observability(old: &ObservabilityConfig, new: &ObservabilityConfig) {
+    if old.log_level != new.log_level {
+        if let Some(handle) = LOG_LAYER_HANDLE.get() {
+            reload_filter("log", &new.log_level, |filter| handle.reload(filter));
+        }
+    }
+
+    for sink in &new.log_sinks {
+        let unchanged = old
+            .log_sinks
+            .iter()
+            .any(|old| old.name == sink.name && old.level == sink.level);
+        let handle = LOG_SINK_HANDLES
+            .get()
+            .and_then(|handles| handles.get(&sink.name));
+
+        if let (false, Some(handle)) = (unchanged, handle) {
+            let name = format!("log sink `{}`", sink.name);
+            reload_filter(&name, &sink.level, |filter| handle.reload(filter));
+        }
+    }
+
+    if old.trace_level != new.trace_level {
+        if let Some(handle) = OTLP_LAYER_HANDLE.get() {
+            reload_filter("trace", &new.trace_level, |filter| handle.reload(filter));
+        }
+    }
+
+    reload_headers_filter(new.headers_filter.clone());
+
+    #[cfg(feature = "tokio-metrics")]
+    if old.metrics_update_interval != new.metrics_update_interval {
+        crate::observability::tokio_metrics::set_tokio_metrics_update_interval(
+            new.metrics_update_interval,
+        );
+    }
+}
+
+fn reload_filter<E: std::fmt::Display>(
+    name: &str,
+    level: &str,
+    reload: impl FnOnce(EnvFilter) -> std::result::Result<(), E>,
+) {
+    let result = EnvFilter::from_str(level)
+        .map_err(|err| err.to_string())
+        .and_then(|filter| reload(filter).map_err(|err| err.to_string()));
+
+    if let Err(err) = result {
+        warn!(target: "config", "Failed to apply {name} level `{level}`: `{err}`.");
+    }
+}
@@ -0,0 +1,247 @@
+use crate::configuration::application::DEFAULT_CONFIG;
+use crate::{AppConfig, Empty, ManagementConfig, ObservabilityConfig};
+use config::{Config, File, FileFormat};
+use serde_json::{json, Value};
+use std::fmt::Write;
+
+const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+const PROPERTIES: &str = "properties";
+const DESCRIPTION: &str = "description";
+const DEFAULT: &str = "default";
+const EXAMPLES: &str = "examples";
+
+/// Describes configuration keys with [`JSON Schema`](https://json-schema.org).\
+/// Implement it for your `ConfigExt` to merge its keys into [`AppConfig::json_schema`]:
+/// ```
+/// use fregate::serde_json::{json, Value};
+/// use fregate::{AppConfig, ConfigSchema};
+///
+/// struct Private;
+///
+/// impl ConfigSchema for Private {
+///     fn schema() -> Value {
+///         json!({
+///             "type": "object",
+///             "properties": {
+///                 "database": {
+///                     "type": "object",
+///                     "properties": {
+///                         "url": { "type": "string", "description": "database connection string" }
+///                     }
+///                 }
+///             }
+///         })
+///     }
+/// }
+///
+/// let schema = AppConfig::<Private>::json_schema();
+/// assert!(schema.pointer("/properties/database/properties/url").is_some());
+/// assert!(schema.pointer("/properties/log/properties/level").is_some());
+/// ```
+pub trait ConfigSchema {
+    /// Returns schema of keys read by type.
+    fn schema() -> Value;
+}
+
+impl ConfigSchema for Empty {
+    fn schema() -> Value {
+        json!({ "type": "object" })
+    }
+}
+
+impl<ConfigExt: ConfigSchema> AppConfig<ConfigExt> {
+    /// Returns JSON Schema of fregate keys merged with `ConfigExt` schema, defaults are taken from `default_conf.toml`.
+    pub fn json_schema() -> Value {
+        let mut schema = json!({
+            "$schema": JSON_SCHEMA_DIALECT,
+            "title": "fregate configuration",
+            "type": "object",
+            "properties": {
+                "host": { "type": "string", "description": "address to bind server to" },
+                "port": {
+                    "type": "integer",
+                    "minimum": 0,
+                    "maximum": 65535,
+                    "description": "port to bind server to"
+                },
+                "profiles": {
+                    "type": ["string", "array"],
+                    "items": { "type": "string" },
+                    "description": "comma separated list of active profiles, see ConfigSource::Profiled",
+                    "examples": ["dev,local"]
+                },
+                "config": {
+                    "type": "object",
+                    "properties": {
+                        "provenance": {
+                            "type": "boolean",
+                            "description": "record which source supplied each value, see AppConfig::explain"
+                        }
+                    }
+                },
+                "private": {
+                    "type": "object",
+                    "description": "application specific section, might be read by `ConfigExt`"
+                },
+                "management": ManagementConfig::schema(),
+                "server": {
+                    "type": "object",
+                    "properties": {
+                        "tls": {
+                            "type": "object",
+                            "description": "used with `tls` feature",
+                            "properties": {
+                                "handshake_timeout": { "type": ["integer", "string"], "description": "TLS handshake timeout, e.g. `10s`, bare number is read as milliseconds" },
+                                "key": {
+                                    "type": "object",
+                                    "properties": {
+                                        "path": { "type": "string", "description": "path to TLS key file", "examples": ["/tls.key"] }
+                                    }
+                                },
+                                "cert": {
+                                    "type": "object",
+                                    "properties": {
+                                        "path": { "type": "string", "description": "path to TLS certificate file", "examples": ["/tls.cert"] }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        });
+
+        merge(&mut schema, ObservabilityConfig::schema());
+        merge(&mut schema, ConfigExt::schema());
+
+        let defaults = Config::builder()
+            .add_source(File::from_str(DEFAULT_CONFIG, FileFormat::Toml))
+            .build()
+            .and_then(|config| config.try_deserialize::<Value>())
+            .unwrap_or_default();
+        fill_defaults(&mut schema, &defaults);
+
+        schema
+    }
+
+    /// Returns TOML configuration listing every key of [`AppConfig::json_schema`] with its description.\
+    /// Keys with defaults are set, keys without defaults are commented out with example value.
+    pub fn sample_config() -> String {
+        let mut sample = String::from(
+            "# fregate configuration, generated from AppConfig::json_schema.\n# Every key might be overridden with environment variables, e.g. `{PREFIX}_LOG_LEVEL=debug`.\n",
+        );
+
+        write_table(&mut sample, "", &Self::json_schema());
+        sample
+    }
+}
+
+/// Merges `other` into `schema`, objects are merged recursively and `required` lists are joined.
+fn merge(schema: &mut Value, other: Value) {
+    match (schema, other) {
+        (Value::Object(schema), Value::Object(other)) => {
+            for (key, value) in other {
+                match (schema.get_mut(&key), value) {
+                    (Some(Value::Array(required)), Value::Array(other)) if key == "required" => {
+                        for value in other {
+                            if !required.contains(&value) {
+                                required.push(value);
+                            }
+                        }
+                    }
+                    (Some(existing), value) => merge(existing, value),
+                    (None, value) => {
+                        schema.insert(key, value);
+                    }
+                }
+            }
+        }
+        (schema, other) => *schema = other,
+    }
+}
+
+fn fill_defaults(schema: &mut Value, defaults: &Value) {
+    let Some(properties) = schema.get_mut(PROPERTIES).and_then(Value::as_object_mut) else {
+        return;
+    };
+
+    for (key, property) in properties {
+        let Some(default) = defaults.get(key) else {
+            continue;
+        };
+
+        if property.get(PROPERTIES).is_some() {
+            fill_defaults(property, default);
+        } else if let Some(property) = property.as_object_mut() {
+            property.entry(DEFAULT).or_insert_with(|| default.clone());
+        }
+    }
+}
+
+fn write_table(sample: &mut String, path: &str, schema: &Value) {
+    let Some(properties) = schema.get(PROPERTIES).and_then(Value::as_object) else {
+        return;
+    };
+
+    let (tables, keys): (Vec<_>, Vec<_>) = properties
+        .iter()
+        .partition(|(_, property)| property.get(PROPERTIES).is_some());
+
+    if !path.is_empty() && !keys.is_empty() {
+        let _ = writeln!(sample);
+        write_description(sample, schema);
+        let _ = writeln!(sample, "[{path}]");
+    }
+
+    for (key, property) in keys {
+        write_description(sample, property);
+
+        if let Some(default) = property.get(DEFAULT) {
+            let _ = writeln!(sample, "{key} = {}", to_toml(default));
+        } else if let Some(example) = property
+            .get(EXAMPLES)
+            .and_then(Value::as_array)
+            .and_then(|examples| examples.first())
+        {
+            let _ = writeln!(sample, "#{key} = {}", to_toml(example));
+        } else {
+            let _ = writeln!(sample, "#{key} =");
+        }
+    }
+
+    for (key, table) in tables {
+        let path = if path.is_empty() {
+            key.clone()
+        } else {
+            format!("{path}.{key}")
+        };
+
+        write_table(sample, &path, table);
+    }
+}
+
+fn write_description(sample: &mut String, schema: &Value) {
+    if let Some(description) = schema.get(DESCRIPTION).and_then(Value::as_str) {
+        for line in description.lines() {
+            let _ = writeln!(sample, "# {line}");
+        }
+    }
+}
+
+fn to_toml(value: &Value) -> String {
+    match value {
+        Value::Null => "\"\"".to_owned(),
+        Value::Array(values) => {
+            let values = values.iter().map(to_toml).collect::<Vec<_>>();
+            format!("[{}]", values.join(", "))
+        }
+        Value::Object(values) => {
+            let values = values
+                .iter()
+                .map(|(key, value)| format!("{key} = {}", to_toml(value)))
+                .collect::<Vec<_>>();
+            format!("{{ {} }}", values.join(", "))
+        }
+        value => value.to_string(),
+    }
+}
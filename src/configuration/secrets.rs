@@ -0,0 +1,338 @@
+use crate::configuration::application::key_pointer;
+use crate::observability::SANITIZED_VALUE;
+use config::{ConfigError, Environment, Map, Source, Value, ValueKind};
+use serde::Deserialize;
+use std::collections::BTreeSet;
+use std::fmt::{Debug, Formatter};
+use std::path::{Path, PathBuf};
+
+const FILE_SUFFIX: &str = "_FILE";
+const HIDDEN_PREFIX: &str = "..";
+
+/// Value which is hidden in [`Debug`] output, e.g. password read with [`crate::ConfigSource::SecretsDir`]:
+/// ```
+/// use fregate::{AppConfig, ConfigSource, Secret};
+/// use fregate::serde_json::json;
+/// use serde::Deserialize;
+///
+/// #[derive(Debug, Deserialize)]
+/// struct Private {
+///     password: Secret<String>,
+/// }
+///
+/// let config = AppConfig::<Private>::load_from([ConfigSource::Value(json!({ "password": "qwerty" }))])?;
+///
+/// assert_eq!(config.private.password.expose(), "qwerty");
+/// assert!(!format!("{config:?}").contains("qwerty"));
+/// # Ok::<(), fregate::error::Error>(())
+/// ```
+#[derive(Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(transparent)]
+pub struct Secret<T>(T);
+
+impl<T> Secret<T> {
+    /// Wraps value.
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// Returns wrapped value.
+    pub fn expose(&self) -> &T {
+        &self.0
+    }
+
+    /// Returns wrapped value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for Secret<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> Debug for Secret<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(SANITIZED_VALUE, f)
+    }
+}
+
+fn read_secret(path: &Path) -> Result<String, ConfigError> {
+    let secret = std::fs::read_to_string(path).map_err(|err| {
+        ConfigError::Message(format!("Can't read secret `{}`: `{err}`.", path.display()))
+    })?;
+
+    Ok(secret.trim_end_matches(['\r', '\n']).to_owned())
+}
+
+/// Maps each file in directory onto config key built from its relative path: `dir/db/password` is read to `/db/password`.\
+/// Entries starting with `..` are skipped as they are used by Kubernetes for atomic secret updates.
+#[derive(Debug, Clone)]
+pub(crate) struct SecretsDir {
+    path: PathBuf,
+}
+
+impl SecretsDir {
+    pub(crate) fn new(path: &str) -> Self {
+        Self {
+            path: PathBuf::from(path),
+        }
+    }
+}
+
+impl Source for SecretsDir {
+    fn clone_into_box(&self) -> Box<dyn Source + Send + Sync> {
+        Box::new(self.clone())
+    }
+
+    fn collect(&self) -> Result<Map<String, Value>, ConfigError> {
+        let origin = self.path.display().to_string();
+        let mut values = Map::new();
+
+        for file in secret_files(&self.path)? {
+            let key = file
+                .strip_prefix(&self.path)
+                .unwrap_or(&file)
+                .iter()
+                .map(|component| component.to_string_lossy())
+                .collect::<Vec<_>>()
+                .join(".");
+
+            let secret = read_secret(&file)?;
+            values.insert(key, Value::new(Some(&origin), ValueKind::String(secret)));
+        }
+
+        Ok(values)
+    }
+}
+
+/// Returns all files in directory and its subdirectories, following symlinks.
+pub(crate) fn secret_files(dir: &Path) -> Result<Vec<PathBuf>, ConfigError> {
+    let entries = std::fs::read_dir(dir).map_err(|err| {
+        ConfigError::Message(format!(
+            "Can't read secrets directory `{}`: `{err}`.",
+            dir.display()
+        ))
+    })?;
+
+    let mut files = Vec::new();
+
+    for entry in entries.flatten() {
+        if entry
+            .file_name()
+            .to_string_lossy()
+            .starts_with(HIDDEN_PREFIX)
+        {
+            continue;
+        }
+
+        let path = entry.path();
+        if path.is_dir() {
+            files.extend(secret_files(&path)?);
+        } else if path.is_file() {
+            files.push(path);
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+/// Environment variables with `{PREFIX}_` prefix except `{PREFIX}_{KEY}_FILE` ones, which are read by [`EnvFiles`].
+#[derive(Debug, Clone)]
+pub(crate) struct EnvValues {
+    prefix: String,
+    separator: String,
+}
+
+impl EnvValues {
+    pub(crate) fn new(prefix: &str, separator: &str) -> Self {
+        Self {
+            prefix: prefix.to_owned(),
+            separator: separator.to_owned(),
+        }
+    }
+}
+
+impl Source for EnvValues {
+    fn clone_into_box(&self) -> Box<dyn Source + Send + Sync> {
+        Box::new(self.clone())
+    }
+
+    fn collect(&self) -> Result<Map<String, Value>, ConfigError> {
+        let vars = std::env::vars()
+            .filter(|(var, _)| file_var_key(var, &self.prefix, &self.separator).is_none())
+            .collect();
+
+        Environment::with_prefix(&self.prefix)
+            .try_parsing(true)
+            .separator(&self.separator)
+            .source(Some(vars))
+            .collect()
+    }
+}
+
+/// Reads files set by `{PREFIX}_{KEY}_FILE` environment variables into `{KEY}`, e.g. `db.password` is read from `{PREFIX}_DB_PASSWORD_FILE`.
+#[derive(Debug, Clone)]
+pub(crate) struct EnvFiles {
+    prefix: String,
+    separator: String,
+}
+
+impl EnvFiles {
+    pub(crate) fn new(prefix: &str, separator: &str) -> Self {
+        Self {
+            prefix: prefix.to_owned(),
+            separator: separator.to_owned(),
+        }
+    }
+
+    /// Variables with their keys.
+    fn vars(&self) -> Vec<(String, String, String)> {
+        let mut vars = std::env::vars()
+            .filter_map(|(var, path)| {
+                let key = file_var_key(&var, &self.prefix, &self.separator)?;
+                Some((var, key, path))
+            })
+            .collect::<Vec<_>>();
+
+        vars.sort();
+        vars
+    }
+
+    /// Files set by environment variables.
+    pub(crate) fn files(&self) -> Vec<PathBuf> {
+        self.vars()
+            .into_iter()
+            .map(|(_, _, path)| PathBuf::from(path))
+            .collect()
+    }
+}
+
+impl Source for EnvFiles {
+    fn clone_into_box(&self) -> Box<dyn Source + Send + Sync> {
+        Box::new(self.clone())
+    }
+
+    fn collect(&self) -> Result<Map<String, Value>, ConfigError> {
+        let mut values = Map::new();
+
+        for (var, key, path) in self.vars() {
+            let secret = read_secret(Path::new(&path))?;
+            values.insert(key, Value::new(Some(&var), ValueKind::String(secret)));
+        }
+
+        Ok(values)
+    }
+}
+
+/// Returns key of `{PREFIX}_{KEY}_FILE` variable, e.g. `db.password` for `{PREFIX}_DB_PASSWORD_FILE`.
+fn file_var_key(var: &str, prefix: &str, separator: &str) -> Option<String> {
+    let var = var.to_lowercase();
+    let prefix = format!("{prefix}{separator}").to_lowercase();
+    let key = var
+        .strip_prefix(&prefix)?
+        .strip_suffix(&FILE_SUFFIX.to_lowercase())?;
+
+    (!key.is_empty()).then(|| key.replace(separator, "."))
+}
+
+/// Keys which values were read from secret files, e.g. with [`crate::ConfigSource::SecretsDir`] or `{PREFIX}_{KEY}_FILE` variables, or decrypted.\
+/// Their values are replaced with `*****` in [`crate::AppConfig::tree`] and wherever they appear in [`crate::AppConfig`] debug output,
+/// deserialize them into [`crate::AppConfig::private`] to read them.
+#[derive(Clone, Default)]
+pub struct SensitiveKeys {
+    keys: BTreeSet<String>,
+    values: Vec<String>,
+}
+
+impl SensitiveKeys {
+    /// Replaces values of keys in tree with `*****` keeping them to hide in debug output.
+    pub(crate) fn redact<'a, I>(keys: I, tree: &mut serde_json::Value) -> Self
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let keys = keys
+            .into_iter()
+            .map(ToOwned::to_owned)
+            .collect::<BTreeSet<_>>();
+        let mut values = Vec::new();
+
+        for key in &keys {
+            // array items are keyed as `servers[0].password`
+            let pointer = key_pointer(&key.replace('[', ".").replace(']', ""));
+            if let Some(value) = tree.pointer_mut(&pointer) {
+                match value.take() {
+                    serde_json::Value::String(secret) => values.push(secret),
+                    other => values.push(other.to_string()),
+                }
+                *value = serde_json::Value::String(SANITIZED_VALUE.to_owned());
+            }
+        }
+
+        // longer values first, so value containing another one is hidden as a whole
+        values.retain(|value| !value.is_empty());
+        values.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+        values.dedup();
+
+        Self { keys, values }
+    }
+
+    /// Returns `true` if value of key, nested keys are separated with `.`, was read from secret file.
+    pub fn contains(&self, key: &str) -> bool {
+        self.keys.contains(key)
+    }
+
+    /// Returns sensitive keys in lexical order.
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.keys.iter().map(String::as_str)
+    }
+
+    /// Returns `true` if there are no sensitive keys.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Replaces sensitive values in text, both as they are and escaped by [`Debug`].
+    fn hide(&self, text: String) -> String {
+        self.values.iter().fold(text, |text, value| {
+            let escaped = format!("{value:?}");
+            let escaped = escaped
+                .strip_prefix('"')
+                .and_then(|escaped| escaped.strip_suffix('"'))
+                .unwrap_or(value);
+
+            text.replace(value, SANITIZED_VALUE)
+                .replace(escaped, SANITIZED_VALUE)
+        })
+    }
+}
+
+impl Debug for SensitiveKeys {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SensitiveKeys")
+            .field("keys", &self.keys)
+            .finish()
+    }
+}
+
+/// Formats value with [`Debug`] hiding values of [`SensitiveKeys`].
+pub(crate) struct Redacted<'a, T>(pub(crate) &'a T, pub(crate) &'a SensitiveKeys);
+
+impl<T: Debug> Debug for Redacted<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let Self(value, sensitive) = self;
+        if sensitive.values.is_empty() {
+            return value.fmt(f);
+        }
+
+        let text = if f.alternate() {
+            format!("{value:#?}")
+        } else {
+            format!("{value:?}")
+        };
+        f.write_str(&sensitive.hide(text))
+    }
+}
@@ -1,4 +1,5 @@
 use config::FileFormat;
+use std::collections::HashMap;
 
 /// Enum to specify configuration source type:
 #[derive(Clone, Debug)]
@@ -7,6 +8,31 @@ pub enum ConfigSource<'a> {
     String(&'a str, FileFormat),
     /// Read file by given path
     File(&'a str),
-    /// Read environment variables with specified prefix
+    /// Read every `toml`, `yaml` and `json` file in directory in lexical order, later files override earlier ones.\
+    /// Hidden entries are skipped, Kubernetes `..data` symlink is resolved once so mounted ConfigMaps are read as one update.
+    Dir(&'a str),
+    /// Read base file by given path and then `{path}.{profile}` file for each active profile, e.g. `./config/app.dev.yaml`.\
+    /// Active profiles are read as comma-separated list from `APP_PROFILES` environment variable or from `profiles` key of previous sources
+    /// and are available in [`crate::AppConfig::profiles`].
+    Profiled(&'a str),
+    /// Read variables from `.env` file at given path without changing process environment.\
+    /// Variables are filtered by `OTEL` and each [`ConfigSource::EnvPrefix`] prefix of the same load, missing file is not an error.
+    DotEnv(&'a str),
+    /// Read environment variables with specified prefix.\
+    /// `{PREFIX}_{KEY}_FILE` variable is read as path to file with value of `{KEY}`, e.g. `TEST_DB_PASSWORD_FILE=/run/secrets/db_password` is read to `/db/password`.
+    /// File which can't be read is an error, such values are hidden, see [`crate::SensitiveKeys`].
     EnvPrefix(&'a str),
+    /// Read each file in directory as value of key built from its relative path, e.g. `dir/db/password` is read to `/db/password`.\
+    /// Such values are hidden, see [`crate::SensitiveKeys`].
+    SecretsDir(&'a str),
+    /// Read command-line arguments: `--port 9000`, `--log.level=debug` or `--set server.tls.handshake_timeout=500`.\
+    /// `--<key>` is read for fregate keys only, other flags are left to application, `--set` accepts any key.
+    /// If `--help` or `-h` is given loading fails with [`crate::error::Error::HelpRequested`] keeping help with known keys and their defaults, see [`crate::AppConfig::args_help`].\
+    /// Arguments are applied after all other sources wherever they are listed.
+    Args,
+    /// Read top-level keys of JSON object, e.g. `json!({"log": {"level": "debug"}})`.
+    Value(serde_json::Value),
+    /// Read values by keys with nested keys separated with `.`, e.g. `("log.level", "debug")`.\
+    /// Values are parsed the same way as environment variables.
+    Map(HashMap<String, String>),
 }
@@ -0,0 +1,190 @@
+use crate::configuration::application::{DEFAULT_SEPARATOR, OTEL_PREFIX};
+use crate::ValidationErrors;
+use config::{Environment, Source};
+use serde_json::Value;
+
+const PROPERTIES: &str = "properties";
+const ADDITIONAL_PROPERTIES: &str = "additionalProperties";
+const MAX_SUGGESTION_DISTANCE: usize = 3;
+
+/// How [`crate::AppConfigBuilder::strict`] reports keys which are not consumed by fregate or `ConfigExt`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StrictMode {
+    /// keep unknown keys in [`crate::AppConfig::unknown_keys`], [`crate::bootstrap()`] logs them as warnings
+    Warn,
+    /// fail [`crate::AppConfigBuilder::build`] with [`crate::error::Error::ValidationError`]
+    Deny,
+}
+
+/// Adds every key of `config` which is not described by `schema`, with closest known key as suggestion.\
+/// Keys set by `OTEL` environment variables, e.g. `OTEL_EXPORTER_OTLP_ENDPOINT`, are read by OpenTelemetry and never reported.
+pub(crate) fn unknown_keys(config: &Value, schema: &Value, errors: &mut ValidationErrors) {
+    let mut keys = Vec::new();
+    flatten_config(String::new(), config, &mut keys);
+
+    let mut known = Vec::new();
+    flatten_schema(String::new(), schema, &mut known);
+
+    let otel_keys = otel_keys();
+
+    for key in keys {
+        let path = key.split('/').skip(1).collect::<Vec<_>>();
+
+        if is_known(schema, &path) || otel_keys.contains(&key) {
+            continue;
+        }
+
+        match suggest(&key, &known) {
+            Some(suggestion) => {
+                errors.add(&key, format!("unknown key, did you mean `{suggestion}`?"))
+            }
+            None => errors.add(&key, "unknown key"),
+        }
+    }
+}
+
+fn otel_keys() -> Vec<String> {
+    Environment::with_prefix(OTEL_PREFIX)
+        .separator(DEFAULT_SEPARATOR)
+        .collect()
+        .map(|values| {
+            values
+                .keys()
+                .map(|key| format!("/{}", key.to_lowercase().replace('.', "/")))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn flatten_config(pointer: String, value: &Value, keys: &mut Vec<String>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                flatten_config(format!("{pointer}/{}", key.to_lowercase()), value, keys);
+            }
+        }
+        _ => keys.push(pointer),
+    }
+}
+
+fn flatten_schema(pointer: String, schema: &Value, keys: &mut Vec<String>) {
+    let Some(properties) = schema.get(PROPERTIES).and_then(Value::as_object) else {
+        return;
+    };
+
+    for (key, property) in properties {
+        let pointer = format!("{pointer}/{}", key.to_lowercase());
+        flatten_schema(pointer.clone(), property, keys);
+        keys.push(pointer);
+    }
+}
+
+/// Key is known if schema describes it or one of its parents accepts any nested keys.
+pub(crate) fn is_known(schema: &Value, path: &[&str]) -> bool {
+    let Some((key, rest)) = path.split_first() else {
+        return true;
+    };
+
+    let additional = schema
+        .get(ADDITIONAL_PROPERTIES)
+        .is_some_and(|additional| additional != &Value::Bool(false));
+
+    match schema.get(PROPERTIES).and_then(Value::as_object) {
+        Some(properties) => properties
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(key))
+            .map_or(additional, |(_, property)| is_known(property, rest)),
+        None => true,
+    }
+}
+
+fn suggest<'a>(key: &str, known: &'a [String]) -> Option<&'a str> {
+    known
+        .iter()
+        .map(|candidate| (edit_distance(key, candidate), candidate))
+        .filter(|(distance, _)| *distance <= MAX_SUGGESTION_DISTANCE.min(key.len() / 3).max(1))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate.as_str())
+}
+
+/// Levenshtein distance.
+#[allow(clippy::indexing_slicing)]
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, a) in a.chars().enumerate() {
+        current[0] = i + 1;
+
+        for (j, b) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a != *b);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod strict_test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn edit_distance_test() {
+        assert_eq!(edit_distance("level", "levle"), 2);
+        assert_eq!(edit_distance("managment", "management"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("same", "same"), 0);
+    }
+
+    #[test]
+    fn unknown_keys_test() {
+        let schema = json!({
+            "properties": {
+                "log": { "properties": { "level": {} } },
+                "private": { "type": "object" },
+                "open": { "properties": {}, "additionalProperties": true }
+            }
+        });
+        let config = json!({
+            "log": { "levle": "info", "level": "info" },
+            "private": { "anything": 1 },
+            "open": { "anything": 1 },
+            "unrelated": true
+        });
+
+        let mut errors = ValidationErrors::default();
+        unknown_keys(&config, &schema, &mut errors);
+
+        let errors = errors
+            .errors()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            errors,
+            [
+                "/log/levle: unknown key, did you mean `/log/level`?",
+                "/unrelated: unknown key"
+            ]
+        );
+    }
+
+    #[test]
+    fn otel_keys_test() {
+        std::env::set_var("OTEL_RESOURCE_ATTRIBUTES", "deployment.environment=test");
+
+        let schema = json!({ "properties": { "log": {} } });
+        let config = json!({ "resource": { "attributes": "deployment.environment=test" } });
+
+        let mut errors = ValidationErrors::default();
+        unknown_keys(&config, &schema, &mut errors);
+
+        assert!(errors.is_empty());
+    }
+}
@@ -1,3 +1,4 @@
+use crate::configuration::units::DurationValue;
 use crate::extensions::DeserializeExt;
 use serde::{Deserialize, Deserializer};
 use serde_json::Value;
@@ -24,8 +25,8 @@ impl<'de> Deserialize<'de> for TlsConfigurationVariables {
     {
         let config = Value::deserialize(deserializer)?;
 
-        let tls_handshake_timeout =
-            config.pointer_and_deserialize::<u64, D::Error>(TLS_HANDSHAKE_TIMEOUT)?;
+        let DurationValue(tls_handshake_timeout) =
+            config.pointer_and_deserialize(TLS_HANDSHAKE_TIMEOUT)?;
         let tls_key_path = config
             .pointer_and_deserialize::<_, D::Error>(TLS_KEY_PATH)
             .ok();
@@ -34,7 +35,7 @@ impl<'de> Deserialize<'de> for TlsConfigurationVariables {
             .ok();
 
         Ok(Self {
-            handshake_timeout: Duration::from_millis(tls_handshake_timeout),
+            handshake_timeout: tls_handshake_timeout,
             key_path: tls_key_path,
             cert_path: tls_cert_path,
         })
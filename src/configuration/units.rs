@@ -0,0 +1,226 @@
+use serde::de::Error;
+use serde::{Deserialize, Deserializer};
+use std::time::Duration;
+
+const DURATION_UNITS: [(&str, f64); 9] = [
+    ("ns", 1e-9),
+    ("us", 1e-6),
+    ("µs", 1e-6),
+    ("ms", 1e-3),
+    ("s", 1.0),
+    ("min", 60.0),
+    ("m", 60.0),
+    ("h", 3600.0),
+    ("d", 86400.0),
+];
+
+const SIZE_UNITS: [(&str, f64); 9] = [
+    ("b", 1.0),
+    ("kb", 1e3),
+    ("kib", 1024.0),
+    ("mb", 1e6),
+    ("mib", 1_048_576.0),
+    ("gb", 1e9),
+    ("gib", 1_073_741_824.0),
+    ("tb", 1e12),
+    ("tib", 1_099_511_627_776.0),
+];
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum NumberOrString {
+    Number(u64),
+    String(String),
+}
+
+/// Parses duration like `500ms`, `10s`, `1.5h` or `1m30s`, bare number is read as milliseconds.\
+/// Supported units: `ns`, `us`, `ms`, `s`, `m` (or `min`), `h`, `d`.
+/// ```
+/// use fregate::parse_duration;
+/// use std::time::Duration;
+///
+/// assert_eq!(parse_duration("1m30s"), Ok(Duration::from_secs(90)));
+/// assert_eq!(parse_duration("500"), Ok(Duration::from_millis(500)));
+/// ```
+pub fn parse_duration(str: &str) -> Result<Duration, String> {
+    let str = str.trim();
+
+    if let Ok(millis) = str.parse::<u64>() {
+        return Ok(Duration::from_millis(millis));
+    }
+
+    if str.is_empty() {
+        return Err("empty duration".to_owned());
+    }
+
+    let mut seconds = 0.0;
+    let mut rest = str;
+
+    while !rest.is_empty() {
+        let (number, unit, after) = split_number(rest).ok_or_else(|| {
+            format!("invalid duration `{str}`, expected e.g. `500ms`, `10s` or `1m30s`")
+        })?;
+        let (_, multiplier) = DURATION_UNITS
+            .iter()
+            .find(|(name, _)| *name == unit)
+            .ok_or_else(|| format!("unknown duration unit `{unit}` in `{str}`"))?;
+
+        seconds += number * multiplier;
+        rest = after;
+    }
+
+    Duration::try_from_secs_f64(seconds).map_err(|err| format!("invalid duration `{str}`: {err}"))
+}
+
+/// Parses size like `512B`, `8KiB`, `1.5MB`, units are case insensitive, bare number is read as bytes.\
+/// Supported units: `B`, `KB`, `KiB`, `MB`, `MiB`, `GB`, `GiB`, `TB`, `TiB`.
+/// ```
+/// use fregate::parse_size;
+///
+/// assert_eq!(parse_size("8KiB"), Ok(8192));
+/// assert_eq!(parse_size("8192"), Ok(8192));
+/// ```
+pub fn parse_size(str: &str) -> Result<usize, String> {
+    let str = str.trim();
+
+    if let Ok(bytes) = str.parse::<usize>() {
+        return Ok(bytes);
+    }
+
+    let (number, unit, _) = split_number(str)
+        .filter(|(_, _, rest)| rest.is_empty())
+        .ok_or_else(|| format!("invalid size `{str}`, expected e.g. `512B`, `8KiB` or `1MB`"))?;
+    let unit = unit.to_lowercase();
+    let (_, multiplier) = SIZE_UNITS
+        .iter()
+        .find(|(name, _)| *name == unit)
+        .ok_or_else(|| format!("unknown size unit `{unit}` in `{str}`"))?;
+
+    let bytes = (number * multiplier).round();
+
+    if bytes < 0.0 || bytes > usize::MAX as f64 {
+        return Err(format!("size `{str}` is out of range"));
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    Ok(bytes as usize)
+}
+
+/// Splits leading number and unit, returns rest of string.
+fn split_number(str: &str) -> Option<(f64, &str, &str)> {
+    let str = str.trim_start();
+    let number_end = str
+        .find(|char: char| !char.is_ascii_digit() && char != '.')
+        .unwrap_or(str.len());
+    let (number, rest) = str.split_at(number_end);
+    let number = number.parse::<f64>().ok()?;
+
+    let rest = rest.trim_start();
+    let unit_end = rest
+        .find(|char: char| char.is_ascii_digit() || char.is_whitespace() || char == '.')
+        .unwrap_or(rest.len());
+    let (unit, rest) = rest.split_at(unit_end);
+
+    (!unit.is_empty()).then_some((number, unit, rest.trim_start()))
+}
+
+/// Deserializes [`Duration`] from string parsed with [`parse_duration`] or from number of milliseconds.
+/// ```
+/// use fregate::deserialize_duration;
+/// use serde::Deserialize;
+/// use std::time::Duration;
+///
+/// #[derive(Deserialize)]
+/// struct Private {
+///     #[serde(deserialize_with = "deserialize_duration")]
+///     timeout: Duration,
+/// }
+/// ```
+pub fn deserialize_duration<'de, D>(deserializer: D) -> Result<Duration, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match NumberOrString::deserialize(deserializer)? {
+        NumberOrString::Number(millis) => Ok(Duration::from_millis(millis)),
+        NumberOrString::String(str) => parse_duration(&str).map_err(D::Error::custom),
+    }
+}
+
+/// Deserializes size in bytes from string parsed with [`parse_size`] or from number of bytes.
+/// ```
+/// use fregate::deserialize_size;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Private {
+///     #[serde(deserialize_with = "deserialize_size")]
+///     max_body: usize,
+/// }
+/// ```
+pub fn deserialize_size<'de, D>(deserializer: D) -> Result<usize, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match NumberOrString::deserialize(deserializer)? {
+        NumberOrString::Number(bytes) => usize::try_from(bytes).map_err(D::Error::custom),
+        NumberOrString::String(str) => parse_size(&str).map_err(D::Error::custom),
+    }
+}
+
+/// [`Duration`] read with [`deserialize_duration`], used with [`crate::extensions::DeserializeExt::pointer_and_deserialize`].
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct DurationValue(pub(crate) Duration);
+
+impl<'de> Deserialize<'de> for DurationValue {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_duration(deserializer).map(Self)
+    }
+}
+
+/// Size read with [`deserialize_size`], used with [`crate::extensions::DeserializeExt::pointer_and_deserialize`].
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct SizeValue(pub(crate) usize);
+
+impl<'de> Deserialize<'de> for SizeValue {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_size(deserializer).map(Self)
+    }
+}
+
+#[cfg(test)]
+mod units_test {
+    use super::*;
+
+    #[test]
+    fn parse_duration_test() {
+        assert_eq!(parse_duration("10s"), Ok(Duration::from_secs(10)));
+        assert_eq!(parse_duration("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_duration("1m30s"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_duration("1.5h"), Ok(Duration::from_secs(5400)));
+        assert_eq!(parse_duration(" 2 min "), Ok(Duration::from_secs(120)));
+        assert_eq!(parse_duration("1000"), Ok(Duration::from_secs(1)));
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("10x").is_err());
+        assert!(parse_duration("s").is_err());
+        assert!(parse_duration("-1s").is_err());
+    }
+
+    #[test]
+    fn parse_size_test() {
+        assert_eq!(parse_size("8KiB"), Ok(8192));
+        assert_eq!(parse_size("8kb"), Ok(8000));
+        assert_eq!(parse_size("1.5 MiB"), Ok(1_572_864));
+        assert_eq!(parse_size("512B"), Ok(512));
+        assert_eq!(parse_size("512"), Ok(512));
+        assert!(parse_size("").is_err());
+        assert!(parse_size("8KiB2").is_err());
+        assert!(parse_size("1a123").is_err());
+        assert!(parse_size("-123").is_err());
+    }
+}
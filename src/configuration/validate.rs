@@ -0,0 +1,129 @@
+use crate::configuration::provenance::Provenance;
+use crate::configuration::{AppConfig, Empty};
+use std::fmt::{Display, Formatter};
+
+/// Checks configuration values which deserialize successfully but are not usable.\
+/// Implemented by fregate configuration sections and might be implemented for `ConfigExt`,
+/// see [`AppConfig::load_validated_from`], [`crate::bootstrap_validated`] and [`crate::AppConfigBuilder::validate_private`].
+/// ```no_run
+/// use fregate::{AppConfig, Validate, ValidationErrors};
+/// use serde::Deserialize;
+///
+/// #[derive(Debug, Deserialize)]
+/// struct Private {
+///     workers: usize,
+/// }
+///
+/// impl Validate for Private {
+///     fn validate(&self, errors: &mut ValidationErrors) {
+///         if self.workers == 0 {
+///             errors.add("/workers", "must be greater than 0");
+///         }
+///     }
+/// }
+///
+/// let config = AppConfig::<Private>::builder()
+///     .add_default()
+///     .add_env_prefixed("TEST")
+///     .validate_private()
+///     .build();
+/// ```
+pub trait Validate {
+    /// Adds every found problem to `errors` with JSON pointer relative to validated value.
+    fn validate(&self, errors: &mut ValidationErrors);
+}
+
+/// Single configuration problem.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    /// JSON pointer to invalid value, e.g. `/log/level`
+    pub pointer: String,
+    /// what is wrong with the value
+    pub message: String,
+    /// source value was read from, if known
+    pub source: Option<String>,
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.pointer, self.message)?;
+
+        if let Some(source) = &self.source {
+            write!(f, " (from {source})")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// All problems found in configuration.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationErrors {
+    prefix: String,
+    errors: Vec<ValidationError>,
+}
+
+impl ValidationErrors {
+    /// Adds problem for value at `pointer`, relative to currently validated value.
+    pub fn add(&mut self, pointer: &str, message: impl Display) {
+        self.errors.push(ValidationError {
+            pointer: format!("{}{pointer}", self.prefix),
+            message: message.to_string(),
+            source: None,
+        });
+    }
+
+    /// Validates `value` nested under `pointer`.
+    pub fn nested<V: Validate + ?Sized>(&mut self, pointer: &str, value: &V) {
+        let prefix_len = self.prefix.len();
+        self.prefix.push_str(pointer);
+        value.validate(self);
+        self.prefix.truncate(prefix_len);
+    }
+
+    /// Returns [`true`] if no problems were found.
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Returns found problems.
+    pub fn errors(&self) -> &[ValidationError] {
+        &self.errors
+    }
+
+    /// Fills [`ValidationError::source`] with name of the last source which sets the value.
+    pub(crate) fn with_sources(mut self, provenance: &Provenance) -> Self {
+        for error in &mut self.errors {
+            let key = error.pointer.trim_start_matches('/').replace('/', ".");
+            error.source = provenance.source_of(&key).map(ToOwned::to_owned);
+        }
+
+        self
+    }
+}
+
+impl Display for ValidationErrors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let errors = self
+            .errors
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+
+        f.write_str(&errors.join("; "))
+    }
+}
+
+impl std::error::Error for ValidationErrors {}
+
+impl Validate for Empty {
+    fn validate(&self, _errors: &mut ValidationErrors) {}
+}
+
+/// Validates fregate sections only, [`AppConfig::private`] is validated if loaded with [`AppConfig::load_validated_from`], [`crate::bootstrap_validated`] or [`crate::AppConfigBuilder::validate_private`].
+impl<ConfigExt> Validate for AppConfig<ConfigExt> {
+    fn validate(&self, errors: &mut ValidationErrors) {
+        self.observability_cfg.validate(errors);
+        errors.nested("/management", &self.management_cfg);
+    }
+}
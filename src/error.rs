@@ -11,6 +11,17 @@ pub enum Error {
     /// Error returned when AppConfigBuilder fails to build configuration
     #[error("Got ConfigError: `{0}`")]
     ConfigError(#[from] ConfigError),
+    /// Error returned when AppConfigBuilder builds configuration with invalid values
+    #[error("Got ValidationError: `{0}`")]
+    ValidationError(#[from] crate::configuration::ValidationErrors),
+    /// Error returned by [`crate::AppConfig::section`] and [`crate::AppConfig::get`] if value is missing or can't be deserialized
+    #[error("Got ConfigSectionError at `{pointer}`: `{message}`")]
+    ConfigSectionError {
+        /// JSON pointer to value
+        pointer: String,
+        /// what is wrong with the value
+        message: String,
+    },
     /// Error returned on init_tracing()
     #[error("Got TraceError: `{0}`")]
     TraceError(#[from] TraceError),
@@ -26,6 +37,14 @@ pub enum Error {
     /// Error returned by serde_json crate
     #[error("Got SerdeError: `{0}`")]
     SerdeError(#[from] serde_json::Error),
+    /// Error returned when [`crate::ConfigSource::Args`] contain `--help` or `-h`, keeps help text to print,
+    /// see [`crate::AppConfig::args_help`]
+    #[error("{0}")]
+    HelpRequested(String),
+    /// Error returned by [`crate::generate_encryption_key`] and [`crate::encrypt_value`]
+    #[cfg(feature = "encryption")]
+    #[error("Got EncryptionError: `{0}`")]
+    EncryptionError(String),
     /// Custom fregate Error
     #[error("Got CustomError: `{0}`")]
     CustomError(String),
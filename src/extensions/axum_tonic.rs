@@ -1,3 +1,4 @@
+use crate::application::routes::register_grpc_path;
 use axum::body::boxed;
 use axum::Router;
 use hyper::{Body, Request, Response};
@@ -42,6 +43,9 @@ impl RouterTonicExt for Router {
             .map_response_body(boxed)
             .service(service);
 
-        Router::new().route_service(&format!("/{}/*rest", S::NAME), svc)
+        let path = format!("/{}/*rest", S::NAME);
+        register_grpc_path(path.clone());
+
+        Router::new().route_service(&path, svc)
     }
 }
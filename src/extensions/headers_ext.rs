@@ -1,10 +1,10 @@
-use crate::observability::{Filter, HeadersFilter, HEADERS_FILTER, SANITIZED_VALUE};
+use crate::observability::{with_headers_filter, Filter, HeadersFilter, SANITIZED_VALUE};
 use axum::headers::{HeaderMap, HeaderName};
 use hyper::http::HeaderValue;
 use std::borrow::Cow;
 
 /// Extension trait to get filtered headers.
-/// Current implementation relies on [`HEADERS_FILTER`].
+/// Current implementation relies on [`crate::observability::HEADERS_FILTER`] or filter applied on configuration reload.
 #[sealed::sealed]
 pub trait HeaderFilterExt
 where
@@ -16,36 +16,34 @@ where
 
 #[sealed::sealed]
 impl HeaderFilterExt for HeaderMap {
-    /// If [`HEADERS_FILTER`] is uninitialised returns [`Cow::Borrowed`] otherwise creates clone and returns [`Cow::Owned`] from included and sanitized fields.
+    /// If [`crate::observability::HEADERS_FILTER`] is uninitialised returns [`Cow::Borrowed`] otherwise creates clone and returns [`Cow::Owned`] from included and sanitized fields.
     fn get_filtered(&self) -> Cow<'_, Self> {
-        HEADERS_FILTER
-            .get()
-            .map(
-                |HeadersFilter {
-                     sanitize,
-                     exclude,
-                     include,
-                 }| {
-                    let filtered = self
-                        .iter()
-                        .map(|(name, value)| {
-                            let lowercase = name.as_str().to_ascii_lowercase();
-                            (lowercase, name, value)
-                        })
-                        .filter_map(|(lowercase, name, value)| {
-                            include_value(include, lowercase, name, value)
-                        })
-                        .filter_map(|(lowercase, name, value)| {
-                            exclude_value(exclude, lowercase, name, value)
-                        })
-                        .map(|(lowercase, name, value)| {
-                            sanitize_value(sanitize, lowercase, name, value)
-                        })
-                        .collect();
-                    Cow::Owned(filtered)
-                },
-            )
-            .unwrap_or(Cow::Borrowed(self))
+        with_headers_filter(
+            |HeadersFilter {
+                 sanitize,
+                 exclude,
+                 include,
+             }| {
+                let filtered = self
+                    .iter()
+                    .map(|(name, value)| {
+                        let lowercase = name.as_str().to_ascii_lowercase();
+                        (lowercase, name, value)
+                    })
+                    .filter_map(|(lowercase, name, value)| {
+                        include_value(include, lowercase, name, value)
+                    })
+                    .filter_map(|(lowercase, name, value)| {
+                        exclude_value(exclude, lowercase, name, value)
+                    })
+                    .map(|(lowercase, name, value)| {
+                        sanitize_value(sanitize, lowercase, name, value)
+                    })
+                    .collect();
+                Cow::Owned(filtered)
+            },
+        )
+        .unwrap_or(Cow::Borrowed(self))
     }
 }
 
@@ -25,6 +25,18 @@ impl DeserializeExt for Value {
             .pointer(pointer)
             .ok_or_else(|| E::missing_field(pointer))?;
 
-        T::deserialize(raw_ret).map_err(E::custom)
+        T::deserialize(raw_ret).or_else(|err| match raw_ret {
+            Value::String(_) => deserialize_lenient(raw_ret).ok_or_else(|| E::custom(err)),
+            _ => Err(E::custom(err)),
+        })
     }
 }
+
+/// Deserializes value with [`config::Value`] conversions, so string like `"9000"` is read as number if `T` expects one.\
+/// Used as fallback for strings which are kept as is by sources, e.g. interpolated `"${PORT}"`.
+pub(crate) fn deserialize_lenient<'de, T: Deserialize<'de>>(value: &Value) -> Option<T> {
+    config::Value::deserialize(value)
+        .ok()?
+        .try_deserialize()
+        .ok()
+}
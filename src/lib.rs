@@ -75,6 +75,7 @@ pub use config;
 #[cfg(feature = "tls")]
 pub use futures_util;
 pub use hyper;
+pub use serde_json;
 pub use thiserror;
 pub use tokio;
 pub use tonic;
@@ -3,7 +3,7 @@ use crate::extensions::DeserializeExt;
 use serde::{Deserialize, Deserializer};
 use serde_json::Value;
 use std::collections::HashSet;
-use std::sync::OnceLock;
+use std::sync::{OnceLock, PoisonError, RwLock};
 
 const SANITIZE_PTR: &str = "/sanitize";
 const INCLUDE_PTR: &str = "/include";
@@ -19,9 +19,37 @@ const EXCLUDE_PTR: &str = "/exclude";
 /// std::env::set_var("TEST_HEADERS_INCLUDE", "*");
 /// ```
 /// In [`crate::extensions::HeaderFilterExt`] trait implementation will have next behaviour:
-/// Include all headers except for "authorization" and sanitize "password,login,client_id" headers.
+/// Include all headers except for "authorization" and sanitize "password,login,client_id" headers.\
+/// Keeps filter set on initialisation, filter applied on configuration reload (see [`crate::ConfigHandle`]) takes precedence over it.
 pub static HEADERS_FILTER: OnceLock<HeadersFilter> = OnceLock::new();
 
+/// Filter applied on configuration reload, inner [`None`] means `headers` were removed from configuration.
+static RELOADED_HEADERS_FILTER: RwLock<Option<Option<HeadersFilter>>> = RwLock::new(None);
+
+/// Calls `f` with filter applied on last configuration reload or with [`HEADERS_FILTER`].\
+/// Returns [`None`] if [`HEADERS_FILTER`] is uninitialised or if reload removed filter.
+pub(crate) fn with_headers_filter<R>(f: impl FnOnce(&HeadersFilter) -> R) -> Option<R> {
+    let initial = HEADERS_FILTER.get()?;
+    let reloaded = RELOADED_HEADERS_FILTER
+        .read()
+        .unwrap_or_else(PoisonError::into_inner);
+
+    match reloaded.as_ref() {
+        Some(reloaded) => reloaded.as_ref().map(f),
+        None => Some(f(initial)),
+    }
+}
+
+/// Replaces filter used by [`crate::extensions::HeaderFilterExt`], [`None`] disables filtering.
+/// Ignored if [`HEADERS_FILTER`] is uninitialised.
+pub(crate) fn reload_headers_filter(filter: Option<HeadersFilter>) {
+    if HEADERS_FILTER.get().is_some() {
+        *RELOADED_HEADERS_FILTER
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = Some(filter);
+    }
+}
+
 /// Headers filter options
 #[derive(Debug, Clone)]
 pub enum Filter {
@@ -83,3 +111,34 @@ fn from_str_to_filter(str: Option<String>) -> Filter {
     })
     .unwrap_or(Filter::Set(HashSet::default()))
 }
+
+#[cfg(test)]
+mod headers_filter_test {
+    use super::*;
+
+    fn filter(include: Filter) -> HeadersFilter {
+        HeadersFilter {
+            include,
+            exclude: Filter::Set(HashSet::default()),
+            sanitize: Filter::Set(HashSet::default()),
+        }
+    }
+
+    #[test]
+    fn reload_and_remove_filter() {
+        let _ = HEADERS_FILTER.set(filter(Filter::All));
+        let includes_all = || with_headers_filter(|filter| matches!(filter.include, Filter::All));
+
+        assert_eq!(includes_all(), Some(true));
+
+        reload_headers_filter(Some(filter(Filter::Set(HashSet::default()))));
+        assert_eq!(includes_all(), Some(false));
+
+        reload_headers_filter(None);
+        assert_eq!(includes_all(), None);
+
+        *RELOADED_HEADERS_FILTER
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = None;
+    }
+}
@@ -1,9 +1,17 @@
+mod callbacks;
+mod openmetrics;
 pub(crate) mod recorder;
+mod series;
 #[cfg(feature = "tokio-metrics")]
 pub mod tokio_metrics;
 
+pub use callbacks::*;
+
 use crate::error::Result;
+use crate::observability::metrics::openmetrics::to_openmetrics;
 use crate::observability::metrics::recorder::{get_handle, get_recorder};
+use crate::observability::metrics::series::{get_series, SeriesRecorder};
+use std::sync::OnceLock;
 
 /// Return rendered metrics.
 /// By default fregate sets `/metrics` endpoint for your [`Application]` which uses [`metrics_exporter_prometheus::PrometheusHandle::render`] fn to get currently available metrics.
@@ -16,9 +24,32 @@ pub fn render_metrics(callback: Option<&(dyn Fn() + Send + Sync + 'static)>) ->
     get_handle().render()
 }
 
+/// Return metrics rendered in [`OpenMetrics`](https://github.com/OpenObservability/OpenMetrics/blob/main/specification/OpenMetrics.md) text format.
+/// Counters and histograms registered after [`init_metrics`] carry `_created` series with time of their first registration.
+/// If metrics are initialised with [`init_metrics_with_exemplars`] they carry exemplar with `trace_id` of the last observation made under an active trace as well.\
+/// Served by `/metrics` endpoint if scraper sends `Accept: application/openmetrics-text` header.
+pub fn render_openmetrics(callback: Option<&(dyn Fn() + Send + Sync + 'static)>) -> String {
+    let prometheus = render_metrics(callback);
+
+    to_openmetrics(&prometheus, get_series())
+}
+
 /// Initialise PrometheusRecorder
 pub fn init_metrics() -> Result<()> {
-    metrics::set_recorder(get_recorder())?;
+    init_metrics_with_exemplars(false)
+}
+
+/// Initialise PrometheusRecorder.
+/// If `exemplars` is [`true`] counters and histograms save `trace_id` of the current span to be rendered by [`render_openmetrics`].
+pub fn init_metrics_with_exemplars(exemplars: bool) -> Result<()> {
+    static RECORDER: OnceLock<SeriesRecorder> = OnceLock::new();
+
+    metrics::set_recorder(RECORDER.get_or_init(|| SeriesRecorder::new(get_recorder(), exemplars)))?;
+
+    metrics::describe_counter!(
+        CALLBACK_FAILURES_COUNTER,
+        "The number of metrics callbacks which failed or timed out."
+    );
 
     #[cfg(feature = "tokio-metrics")]
     tokio_metrics::register_metrics();
@@ -0,0 +1,89 @@
+use metrics::increment_counter;
+use std::fmt::{Debug, Display, Formatter};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::{timeout_at, Instant};
+use tracing::warn;
+
+pub(crate) const CALLBACK_FAILURES_COUNTER: &str = "metrics_callback_failures_total";
+
+type CallbackFuture = Pin<Box<dyn Future<Output = Result<(), String>> + Send>>;
+
+/// Named async callback which is called before metrics are rendered.
+/// See [`run_metrics_callbacks`] and [`crate::Application::metrics_async_callback`].
+#[derive(Clone)]
+pub struct AsyncMetricsCallback {
+    name: Arc<str>,
+    callback: Arc<dyn Fn() -> CallbackFuture + Send + Sync>,
+}
+
+impl AsyncMetricsCallback {
+    /// Creates new [`AsyncMetricsCallback`] with given name, which is used in logs and in `metrics_callback_failures_total` counter labels.
+    pub fn new<F, Fut, E>(name: &str, callback: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), E>> + Send + 'static,
+        E: Display,
+    {
+        let callback = move || -> CallbackFuture {
+            let future = callback();
+            Box::pin(async move { future.await.map_err(|err| err.to_string()) })
+        };
+
+        Self {
+            name: Arc::from(name),
+            callback: Arc::new(callback),
+        }
+    }
+
+    /// Returns callback name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Debug for AsyncMetricsCallback {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AsyncMetricsCallback")
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+/// Runs all callbacks concurrently and waits for them at most `timeout` if given.\
+/// Callback which fails, panics or does not finish in time is logged and counted in `metrics_callback_failures_total` counter with `callback` and `reason` labels.
+pub async fn run_metrics_callbacks(callbacks: &[AsyncMetricsCallback], timeout: Option<Duration>) {
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    let handles = callbacks
+        .iter()
+        .map(|callback| (callback.name(), tokio::spawn((callback.callback)())))
+        .collect::<Vec<_>>();
+
+    for (name, mut handle) in handles {
+        let result = match deadline {
+            Some(deadline) => timeout_at(deadline, &mut handle).await,
+            None => Ok((&mut handle).await),
+        };
+
+        let reason = match result {
+            Ok(Ok(Ok(()))) => continue,
+            Ok(Ok(Err(err))) => {
+                warn!("Metrics callback `{name}` failed: `{err}`.");
+                "error"
+            }
+            Ok(Err(err)) => {
+                warn!("Metrics callback `{name}` panicked: `{err}`.");
+                "panic"
+            }
+            Err(_) => {
+                handle.abort();
+                warn!("Metrics callback `{name}` timed out.");
+                "timeout"
+            }
+        };
+
+        increment_counter!(CALLBACK_FAILURES_COUNTER, "callback" => name.to_owned(), "reason" => reason);
+    }
+}
@@ -0,0 +1,234 @@
+use crate::observability::metrics::series::{Exemplar, SeriesRegistry};
+use std::fmt::Write;
+
+const TOTAL_SUFFIX: &str = "_total";
+const BUCKET_SUFFIX: &str = "_bucket";
+const COUNT_SUFFIX: &str = "_count";
+const CREATED_SUFFIX: &str = "_created";
+const LE_LABEL: &str = "le=\"";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MetricType {
+    Counter,
+    Histogram,
+    Summary,
+    Other,
+}
+
+/// Converts metrics rendered by [`metrics_exporter_prometheus::PrometheusHandle::render`] into [`OpenMetrics`](https://github.com/OpenObservability/OpenMetrics/blob/main/specification/OpenMetrics.md) text format.\
+/// Counter families lose `_total` suffix while their samples get it, empty lines are dropped and `# EOF` is appended.
+/// Counters, histograms and summaries found in `registry` get `_created` sample after `_total` and `_count` samples.
+/// Exemplars are attached to counter samples and to the first histogram bucket containing observed value.
+pub(crate) fn to_openmetrics(prometheus: &str, registry: &SeriesRegistry) -> String {
+    let mut output = String::with_capacity(prometheus.len() + 8);
+    let mut metric_type = MetricType::Other;
+    let mut exemplar_used = false;
+    let mut last_series = String::new();
+    let mut description = None;
+
+    for line in prometheus.lines().filter(|line| !line.is_empty()) {
+        if let Some(help) = line.strip_prefix("# HELP ") {
+            // HELP line comes before TYPE line, so family name is not known yet.
+            description = help.split_once(' ').map(|(_, description)| description);
+        } else if let Some(type_line) = line.strip_prefix("# TYPE ") {
+            let (name, r#type) = type_line.split_once(' ').unwrap_or((type_line, ""));
+            metric_type = match r#type {
+                "counter" => MetricType::Counter,
+                "histogram" => MetricType::Histogram,
+                "summary" => MetricType::Summary,
+                _ => MetricType::Other,
+            };
+            let name = match metric_type {
+                MetricType::Counter => family_name(name),
+                _ => name,
+            };
+            if let Some(description) = description.take() {
+                let _ = writeln!(output, "# HELP {name} {description}");
+            }
+            let _ = writeln!(output, "# TYPE {name} {type}");
+        } else if line.starts_with('#') {
+            output.push_str(line);
+            output.push('\n');
+        } else {
+            let (series, value) = line.rsplit_once(' ').unwrap_or((line, ""));
+            let mut created = None;
+
+            match metric_type {
+                MetricType::Counter => {
+                    let (name, labels) = split_series(series);
+                    let _ = write!(
+                        output,
+                        "{}{TOTAL_SUFFIX}{labels} {value}",
+                        family_name(name)
+                    );
+
+                    if let Some(registered) = registry.get(series) {
+                        if let Some(exemplar) = registered.exemplar() {
+                            write_exemplar(&mut output, &exemplar);
+                        }
+                        created = Some((family_name(name), labels, registered.created()));
+                    }
+                }
+                MetricType::Histogram | MetricType::Summary => {
+                    let _ = write!(output, "{series} {value}");
+
+                    if let Some((key, le)) = bucket_key(series) {
+                        if key != last_series {
+                            last_series = key;
+                            exemplar_used = false;
+                        }
+
+                        let exemplar = registry
+                            .get(&last_series)
+                            .and_then(|registered| registered.exemplar());
+
+                        if let Some(exemplar) = exemplar {
+                            let fits = le == "+Inf"
+                                || le.parse::<f64>().is_ok_and(|le| exemplar.value <= le);
+
+                            if fits && !exemplar_used {
+                                write_exemplar(&mut output, &exemplar);
+                                exemplar_used = true;
+                            }
+                        }
+                    } else {
+                        let (name, labels) = split_series(series);
+
+                        if let Some(name) = name.strip_suffix(COUNT_SUFFIX) {
+                            created = registry
+                                .get(&format!("{name}{labels}"))
+                                .map(|registered| (name, labels, registered.created()));
+                        }
+                    }
+                }
+                MetricType::Other => {
+                    let _ = write!(output, "{series} {value}");
+                }
+            }
+
+            output.push('\n');
+
+            if let Some((name, labels, created)) = created {
+                let _ = writeln!(output, "{name}{CREATED_SUFFIX}{labels} {created:.3}");
+            }
+        }
+    }
+
+    output.push_str("# EOF\n");
+    output
+}
+
+fn family_name(name: &str) -> &str {
+    name.strip_suffix(TOTAL_SUFFIX).unwrap_or(name)
+}
+
+/// Splits `name{labels}` into `name` and `{labels}`.
+fn split_series(series: &str) -> (&str, &str) {
+    series
+        .find('{')
+        .map_or((series, ""), |idx| series.split_at(idx))
+}
+
+/// Splits `name_bucket{labels,le="x"}` into exemplar key `name{labels}` and `x`.
+fn bucket_key(series: &str) -> Option<(String, &str)> {
+    let le_idx = series.rfind(LE_LABEL)?;
+    let le = series.get(le_idx + LE_LABEL.len()..)?.strip_suffix("\"}")?;
+    let (name, labels) = series.get(..le_idx)?.split_once('{')?;
+    let name = name.strip_suffix(BUCKET_SUFFIX)?;
+    let labels = labels.strip_suffix(',').unwrap_or(labels);
+
+    let key = if labels.is_empty() {
+        name.to_owned()
+    } else {
+        format!("{name}{{{labels}}}")
+    };
+
+    Some((key, le))
+}
+
+fn write_exemplar(output: &mut String, exemplar: &Exemplar) {
+    let Exemplar {
+        trace_id,
+        value,
+        timestamp,
+    } = exemplar;
+
+    let _ = write!(
+        output,
+        " # {{trace_id=\"{trace_id}\"}} {value} {timestamp:.3}"
+    );
+}
+
+#[cfg(test)]
+mod openmetrics_test {
+    use super::*;
+
+    #[test]
+    fn convert_test() {
+        let prometheus = "# HELP requests_total Requests.\n\
+            # TYPE requests_total counter\n\
+            requests_total{method=\"GET\"} 3\n\
+            \n\
+            # TYPE errors counter\n\
+            errors 1\n\
+            \n\
+            # HELP temperature_total Not a counter.\n\
+            # TYPE temperature_total gauge\n\
+            temperature_total 36.6\n\
+            \n\
+            # TYPE latency histogram\n\
+            latency_bucket{path=\"/\",le=\"0.1\"} 0\n\
+            latency_bucket{path=\"/\",le=\"1\"} 1\n\
+            latency_bucket{path=\"/\",le=\"+Inf\"} 1\n\
+            latency_sum{path=\"/\"} 0.5\n\
+            latency_count{path=\"/\"} 1\n\
+            \n\
+            # TYPE size summary\n\
+            size{quantile=\"0.5\"} 2\n\
+            size_sum 2\n\
+            size_count 1\n\
+            \n";
+
+        let registry = SeriesRegistry::new();
+        registry
+            .register("requests_total{method=\"GET\"}", 1.0)
+            .save_exemplar(Exemplar {
+                trace_id: "abc".to_owned(),
+                value: 1.0,
+                timestamp: 1.5,
+            });
+        registry
+            .register("latency{path=\"/\"}", 1.25)
+            .save_exemplar(Exemplar {
+                trace_id: "def".to_owned(),
+                value: 0.5,
+                timestamp: 2.0,
+            });
+        registry.register("size", 0.5);
+
+        let expected = "# HELP requests Requests.\n\
+            # TYPE requests counter\n\
+            requests_total{method=\"GET\"} 3 # {trace_id=\"abc\"} 1 1.500\n\
+            requests_created{method=\"GET\"} 1.000\n\
+            # TYPE errors counter\n\
+            errors_total 1\n\
+            # HELP temperature_total Not a counter.\n\
+            # TYPE temperature_total gauge\n\
+            temperature_total 36.6\n\
+            # TYPE latency histogram\n\
+            latency_bucket{path=\"/\",le=\"0.1\"} 0\n\
+            latency_bucket{path=\"/\",le=\"1\"} 1 # {trace_id=\"def\"} 0.5 2.000\n\
+            latency_bucket{path=\"/\",le=\"+Inf\"} 1\n\
+            latency_sum{path=\"/\"} 0.5\n\
+            latency_count{path=\"/\"} 1\n\
+            latency_created{path=\"/\"} 1.250\n\
+            # TYPE size summary\n\
+            size{quantile=\"0.5\"} 2\n\
+            size_sum 2\n\
+            size_count 1\n\
+            size_created 0.500\n\
+            # EOF\n";
+
+        assert_eq!(to_openmetrics(prometheus, &registry), expected);
+    }
+}
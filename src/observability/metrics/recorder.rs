@@ -1,10 +1,20 @@
 use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle, PrometheusRecorder};
 use std::sync::OnceLock;
 
+/// Labels added to every rendered series.
+pub(crate) const GLOBAL_LABELS: &[(&str, &str)] = &[];
+
 pub(crate) fn get_recorder() -> &'static PrometheusRecorder {
     static RECORDER: OnceLock<PrometheusRecorder> = OnceLock::new();
 
-    RECORDER.get_or_init(|| PrometheusBuilder::new().build_recorder())
+    RECORDER.get_or_init(|| {
+        GLOBAL_LABELS
+            .iter()
+            .fold(PrometheusBuilder::new(), |builder, (key, value)| {
+                builder.add_global_label(*key, *value)
+            })
+            .build_recorder()
+    })
 }
 
 pub(crate) fn get_handle() -> &'static PrometheusHandle {
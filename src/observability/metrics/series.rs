@@ -0,0 +1,275 @@
+use crate::observability::metrics::recorder::GLOBAL_LABELS;
+use metrics::{
+    Counter, CounterFn, Gauge, Histogram, HistogramFn, Key, KeyName, Label, Recorder, SharedString,
+    Unit,
+};
+use metrics_exporter_prometheus::formatting::key_to_parts;
+use metrics_exporter_prometheus::PrometheusRecorder;
+use opentelemetry::trace::TraceContextExt;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex, OnceLock, PoisonError, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+const SHARDS: usize = 16;
+
+/// Last observation made under an active trace.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Exemplar {
+    pub(crate) trace_id: String,
+    pub(crate) value: f64,
+    pub(crate) timestamp: f64,
+}
+
+/// Creation time and last exemplar of counter or histogram series.
+#[derive(Debug)]
+pub(crate) struct Series {
+    created: f64,
+    exemplar: Mutex<Option<Exemplar>>,
+}
+
+impl Series {
+    pub(crate) fn new(created: f64) -> Self {
+        Self {
+            created,
+            exemplar: Mutex::new(None),
+        }
+    }
+
+    pub(crate) fn created(&self) -> f64 {
+        self.created
+    }
+
+    pub(crate) fn exemplar(&self) -> Option<Exemplar> {
+        self.exemplar
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    pub(crate) fn save_exemplar(&self, exemplar: Exemplar) {
+        *self.exemplar.lock().unwrap_or_else(PoisonError::into_inner) = Some(exemplar);
+    }
+}
+
+/// Series keyed as rendered in Prometheus text format, e.g. `name{label="value"}`.\
+/// Each series keeps a single exemplar, so memory is bounded by the number of series [`PrometheusRecorder`] renders.
+/// Series are split between shards, observations lock only the series they update.
+#[derive(Debug)]
+pub(crate) struct SeriesRegistry {
+    shards: [RwLock<HashMap<String, Arc<Series>>>; SHARDS],
+}
+
+impl SeriesRegistry {
+    pub(crate) fn new() -> Self {
+        Self {
+            shards: std::array::from_fn(|_| RwLock::default()),
+        }
+    }
+
+    /// Returns registered series or registers new one created at `created`.
+    pub(crate) fn register(&self, series: &str, created: f64) -> Arc<Series> {
+        let shard = self.shard(series);
+
+        if let Some(registered) = shard
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(series)
+        {
+            return registered.clone();
+        }
+
+        shard
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(series.to_owned())
+            .or_insert_with(|| Arc::new(Series::new(created)))
+            .clone()
+    }
+
+    pub(crate) fn get(&self, series: &str) -> Option<Arc<Series>> {
+        self.shard(series)
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(series)
+            .cloned()
+    }
+
+    #[allow(clippy::indexing_slicing, clippy::cast_possible_truncation)]
+    fn shard(&self, series: &str) -> &RwLock<HashMap<String, Arc<Series>>> {
+        let mut hasher = DefaultHasher::new();
+        series.hash(&mut hasher);
+
+        // index is always less than SHARDS
+        &self.shards[hasher.finish() as usize % SHARDS]
+    }
+}
+
+pub(crate) fn get_series() -> &'static SeriesRegistry {
+    static SERIES: OnceLock<SeriesRegistry> = OnceLock::new();
+
+    SERIES.get_or_init(SeriesRegistry::new)
+}
+
+/// [`Recorder`] which delegates to [`PrometheusRecorder`], records creation time of counters and histograms
+/// and, if `exemplars` is set, saves their exemplars.
+pub(crate) struct SeriesRecorder {
+    inner: &'static PrometheusRecorder,
+    registry: &'static SeriesRegistry,
+    exemplars: bool,
+}
+
+impl SeriesRecorder {
+    pub(crate) fn new(inner: &'static PrometheusRecorder, exemplars: bool) -> Self {
+        Self {
+            inner,
+            registry: get_series(),
+            exemplars,
+        }
+    }
+
+    fn register(&self, key: &Key) -> Arc<Series> {
+        self.registry.register(&series(key, GLOBAL_LABELS), now())
+    }
+}
+
+impl Recorder for SeriesRecorder {
+    fn describe_counter(&self, key: KeyName, unit: Option<Unit>, description: SharedString) {
+        self.inner.describe_counter(key, unit, description)
+    }
+
+    fn describe_gauge(&self, key: KeyName, unit: Option<Unit>, description: SharedString) {
+        self.inner.describe_gauge(key, unit, description)
+    }
+
+    fn describe_histogram(&self, key: KeyName, unit: Option<Unit>, description: SharedString) {
+        self.inner.describe_histogram(key, unit, description)
+    }
+
+    fn register_counter(&self, key: &Key) -> Counter {
+        let series = self.register(key);
+        let inner = self.inner.register_counter(key);
+
+        if self.exemplars {
+            Counter::from_arc(Arc::new(WithExemplar { inner, series }))
+        } else {
+            inner
+        }
+    }
+
+    fn register_gauge(&self, key: &Key) -> Gauge {
+        self.inner.register_gauge(key)
+    }
+
+    fn register_histogram(&self, key: &Key) -> Histogram {
+        let series = self.register(key);
+        let inner = self.inner.register_histogram(key);
+
+        if self.exemplars {
+            Histogram::from_arc(Arc::new(WithExemplar { inner, series }))
+        } else {
+            inner
+        }
+    }
+}
+
+struct WithExemplar<T> {
+    inner: T,
+    series: Arc<Series>,
+}
+
+impl<T> WithExemplar<T> {
+    fn save_exemplar(&self, value: f64) {
+        let context = tracing::Span::current().context();
+        let span = context.span();
+        let span_context = span.span_context();
+
+        if !span_context.is_valid() {
+            return;
+        }
+
+        self.series.save_exemplar(Exemplar {
+            trace_id: span_context.trace_id().to_string(),
+            value,
+            timestamp: now(),
+        });
+    }
+}
+
+impl CounterFn for WithExemplar<Counter> {
+    fn increment(&self, value: u64) {
+        self.inner.increment(value);
+        #[allow(clippy::cast_precision_loss)]
+        self.save_exemplar(value as f64);
+    }
+
+    fn absolute(&self, value: u64) {
+        self.inner.absolute(value);
+    }
+}
+
+impl HistogramFn for WithExemplar<Histogram> {
+    fn record(&self, value: f64) {
+        self.inner.record(value);
+        self.save_exemplar(value);
+    }
+}
+
+fn now() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|since_epoch| since_epoch.as_secs_f64())
+        .unwrap_or_default()
+}
+
+/// Builds series the way [`PrometheusRecorder`] renders it: global labels go first and are overridden by key labels.
+fn series(key: &Key, global_labels: &[(&'static str, &'static str)]) -> String {
+    let labels = global_labels
+        .iter()
+        .map(|(key, value)| Label::from_static_parts(key, value))
+        .chain(key.labels().cloned())
+        .collect::<Vec<_>>();
+    let (name, labels) = key_to_parts(&Key::from_parts(key.name().to_owned(), labels), None);
+
+    if labels.is_empty() {
+        name
+    } else {
+        format!("{name}{{{}}}", labels.join(","))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod series_test {
+    use super::*;
+
+    #[test]
+    fn register_keeps_created() {
+        let registry = SeriesRegistry::new();
+
+        let first = registry.register("a", 1.0);
+        let second = registry.register("a", 2.0);
+        registry.register("b", 3.0);
+
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(registry.get("a").unwrap().created(), 1.0);
+        assert_eq!(registry.get("b").unwrap().created(), 3.0);
+        assert!(registry.get("c").is_none());
+    }
+
+    #[test]
+    fn series_with_global_labels() {
+        let key = Key::from_parts(
+            "requests",
+            vec![Label::new("env", "test"), Label::new("method", "GET")],
+        );
+
+        assert_eq!(series(&key, &[]), "requests{env=\"test\",method=\"GET\"}");
+        assert_eq!(
+            series(&key, &[("service", "api"), ("env", "prod")]),
+            "requests{service=\"api\",env=\"test\",method=\"GET\"}"
+        );
+    }
+}
@@ -3,10 +3,24 @@
 use metrics::{
     absolute_counter, describe_counter, describe_gauge, gauge, register_counter, register_gauge,
 };
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
 use tokio::runtime::Handle;
 use tokio_metrics::{RuntimeMetrics, RuntimeMonitor};
 
+static METRICS_UPDATE_INTERVAL_MS: AtomicU64 = AtomicU64::new(1000);
+
+/// Changes update interval of the task started with [`init_tokio_metrics_task`], applied after current interval elapses.
+pub fn set_tokio_metrics_update_interval(metrics_update_interval: Duration) {
+    METRICS_UPDATE_INTERVAL_MS.store(
+        metrics_update_interval
+            .as_millis()
+            .try_into()
+            .unwrap_or(u64::MAX),
+        Ordering::Relaxed,
+    );
+}
+
 /// Initialise key [`metrics`](https://docs.rs/tokio-metrics/latest/tokio_metrics/struct.TaskMetrics.html) of tokio tasks.\
 /// Example:
 /// ```no_run
@@ -29,6 +43,8 @@ use tokio_metrics::{RuntimeMetrics, RuntimeMonitor};
 /// }
 /// ```
 pub fn init_tokio_metrics_task(metrics_update_interval: Duration) {
+    set_tokio_metrics_update_interval(metrics_update_interval);
+
     let handle = Handle::current();
     let runtime_monitor = RuntimeMonitor::new(&handle);
 
@@ -122,7 +138,8 @@ pub fn init_tokio_metrics_task(metrics_update_interval: Duration) {
             absolute_counter!("budget_forced_yield_count", budget_forced_yield_count);
             absolute_counter!("io_driver_ready_count", io_driver_ready_count);
 
-            tokio::time::sleep(metrics_update_interval).await;
+            let metrics_update_interval = METRICS_UPDATE_INTERVAL_MS.load(Ordering::Relaxed);
+            tokio::time::sleep(Duration::from_millis(metrics_update_interval)).await;
         }
     });
 }
@@ -3,17 +3,27 @@
 mod event_formatter;
 pub mod floor_char_boundary;
 mod log_layer;
+mod log_schema;
 mod otlp_layer;
+mod rolling_file;
+mod span_fields;
+mod syslog;
 mod tracing_fields;
 
 pub use event_formatter::*;
 pub use log_layer::*;
+pub use log_schema::*;
 pub use otlp_layer::*;
+pub use rolling_file::*;
+pub use span_fields::*;
+pub use syslog::*;
 pub use tracing_fields::*;
 
+use crate::configuration::ObservabilityConfig;
 use crate::error::Result;
 use crate::observability::{HeadersFilter, HEADERS_FILTER};
 use opentelemetry::global::set_error_handler;
+use std::collections::HashMap;
 use std::sync::OnceLock;
 use tracing_appender::non_blocking::WorkerGuard;
 use tracing_subscriber::layer::Layered;
@@ -35,6 +45,18 @@ pub static LOG_LAYER_HANDLE: OnceLock<LogLayerHandle> = OnceLock::new();
 /// See in [`example`](https://github.com/elefant-dev/fregate-rs/tree/main/examples/log-level-change) how it might be used.
 pub static OTLP_LAYER_HANDLE: OnceLock<TraceLayerHandle> = OnceLock::new();
 
+/// This by default uninitialised unless you call [`crate::bootstrap()`] or [`init_tracing`] functions.
+/// Used to change log level filter of each [`LogSinkConfig`](crate::configuration::LogSinkConfig) by its name.
+pub static LOG_SINK_HANDLES: OnceLock<HashMap<String, LogLayerHandle>> = OnceLock::new();
+
+/// Flushes logs of each of [`ObservabilityConfig::log_sinks`] on drop, returned by [`init_tracing_with_config`] along with [`WorkerGuard`] of main output.\
+/// Holds [`WorkerGuard`] of each sink non-blocking writer, read more here: [`https://docs.rs/tracing-appender/latest/tracing_appender/non_blocking/struct.WorkerGuard.html`]
+#[must_use]
+#[derive(Debug)]
+pub struct LogGuard {
+    _guards: Vec<WorkerGuard>,
+}
+
 /// Alias for [`Handle<EnvFilter, Layered<Option<Box<dyn Layer<Registry> + Send + Sync>>, Registry>>`]
 pub type LogLayerHandle =
     Handle<EnvFilter, Layered<Option<Box<dyn Layer<Registry> + Send + Sync>>, Registry>>;
@@ -49,6 +71,8 @@ pub type TraceLayerHandle = Handle<EnvFilter, Registry>;
 /// 4. [`HEADERS_FILTER`] to be used in [`crate::extensions::HeaderFilterExt`].\
 /// 5. Sets panic hook.\
 /// Uses [`tracing_appender`] crate to do non blocking writes to stdout, so returns [`WorkerGuard`]. Read more here: [`https://docs.rs/tracing-appender/latest/tracing_appender/non_blocking/struct.WorkerGuard.html`]
+///
+/// See [`init_tracing_with_config`] to set log output, format, schema and sinks.
 #[allow(clippy::too_many_arguments)]
 pub fn init_tracing(
     log_level: &str,
@@ -61,23 +85,58 @@ pub fn init_tracing(
     buffered_lines_limit: Option<usize>,
     headers_filter: Option<HeadersFilter>,
 ) -> Result<WorkerGuard> {
-    let (log_layer, log_reload, worker) = log_layer(
-        log_level,
-        version,
-        service_name,
-        component_name,
-        log_msg_length,
+    let (worker, _) = init_tracing_with_config(&ObservabilityConfig {
+        log_level: log_level.to_owned(),
+        trace_level: trace_level.to_owned(),
+        version: version.to_owned(),
+        service_name: service_name.to_owned(),
+        component_name: component_name.to_owned(),
+        traces_endpoint: traces_endpoint.map(ToOwned::to_owned),
+        msg_length: log_msg_length,
         buffered_lines_limit,
+        headers_filter,
+        ..Default::default()
+    })?;
+
+    Ok(worker)
+}
+
+/// Same as [`init_tracing`] with [`ObservabilityConfig`], sets up [`log_layer_with_config()`] for main output and one more for each of `log_sinks`,
+/// all of them write [`LogSchema`] and [`LogSpans`], filter of each sink is reloaded with [`LOG_SINK_HANDLES`].\
+/// Returns [`WorkerGuard`] of main output and [`LogGuard`] of sinks.
+pub fn init_tracing_with_config(config: &ObservabilityConfig) -> Result<(WorkerGuard, LogGuard)> {
+    let (main_layer, log_reload, worker) = log_layer_with_config(
+        config,
+        &config.log_level,
+        &config.log_output,
+        config.log_format,
     )?;
-    let (otlp_layer, otlp_reload) = otlp_layer(trace_level, component_name, traces_endpoint)?;
-    registry().with(otlp_layer).with(log_layer).try_init()?;
+    let mut log_layers = vec![main_layer];
+    let mut guards = Vec::with_capacity(config.log_sinks.len());
+    let mut sink_reloads = HashMap::with_capacity(config.log_sinks.len());
+
+    for sink in &config.log_sinks {
+        let (sink_layer, sink_reload, sink_worker) =
+            log_layer_with_config(config, &sink.level, &sink.output, sink.format)?;
+        log_layers.push(sink_layer);
+        guards.push(sink_worker);
+        sink_reloads.insert(sink.name.clone(), sink_reload);
+    }
+
+    let (otlp_layer, otlp_reload) = otlp_layer(
+        &config.trace_level,
+        &config.component_name,
+        config.traces_endpoint.as_deref(),
+    )?;
+    registry().with(otlp_layer).with(log_layers).try_init()?;
 
     let _ = LOG_LAYER_HANDLE.get_or_init(|| log_reload);
+    let _ = LOG_SINK_HANDLES.get_or_init(|| sink_reloads);
     if let Some(otlp_reload) = otlp_reload {
         let _ = OTLP_LAYER_HANDLE.get_or_init(|| otlp_reload);
     }
-    if let Some(headers_filter) = headers_filter {
-        let _ = HEADERS_FILTER.get_or_init(|| headers_filter);
+    if let Some(headers_filter) = &config.headers_filter {
+        let _ = HEADERS_FILTER.get_or_init(|| headers_filter.clone());
     }
 
     set_error_handler(|err| {
@@ -85,7 +144,7 @@ pub fn init_tracing(
     })?;
     set_panic_hook();
 
-    Ok(worker)
+    Ok((worker, LogGuard { _guards: guards }))
 }
 
 fn set_panic_hook() {
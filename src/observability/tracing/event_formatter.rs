@@ -1,12 +1,16 @@
 //! Fregate [`FormatEvent`] trait implementation
+use crate::configuration::LogFormat;
 use crate::error::{Error, Result};
+use crate::observability::tracing::span_fields::SpanFieldValues;
+use crate::observability::tracing::syslog::priority;
+use crate::observability::{LogField, LogSchema, LogSpans, SpanConflict};
 use opentelemetry::trace::{SpanId, TraceContextExt};
 use serde::{ser::SerializeMap, Serialize, Serializer};
 use serde_json::Value;
 use std::borrow::Cow;
 use std::{collections::BTreeMap, fmt, num::NonZeroU8};
 use time::format_description::well_known::iso8601::{Config, Iso8601, TimePrecision};
-use tracing::{field::Field, Event, Subscriber};
+use tracing::{field::Field, Event, Level, Subscriber};
 use tracing_opentelemetry::OtelData;
 use tracing_subscriber::registry::{Extensions, SpanRef};
 use tracing_subscriber::{
@@ -31,14 +35,19 @@ pub(crate) const TIME: &str = "time";
 pub(crate) const TIMESTAMP: &str = "timestamp";
 pub(crate) const TRACE_ID: &str = "traceId";
 pub(crate) const SPAN_ID: &str = "spanId";
+pub(crate) const SPANS: &str = "spans";
 
-const DEFAULT_FIELDS: [&str; 11] = [
-    VERSION, SERVICE, COMPONENT, TARGET, MSG, LOG_LEVEL, TIME, TIMESTAMP, MESSAGE, TRACE_ID,
-    SPAN_ID,
-];
 const MIN_LOG_MESSAGE_LEN: usize = 256;
-
-/// Custom evet formatter.
+const PRETTY_INDENT: &str = "    ";
+const ANSI_RESET: &str = "\x1b[0m";
+const ANSI_DIMMED: &str = "\x1b[2m";
+const ANSI_BOLD: &str = "\x1b[1m";
+
+/// Custom evet formatter.\
+/// Writes single-line JSON by default, [`LogFormat::Pretty`] and [`LogFormat::Compact`] set with [`EventFormatter::with_format`]
+/// write the same fields as `key=value` text with colored level if writer supports ANSI escapes.\
+/// Keys and level values of JSON are defined by [`LogSchema`] set with [`EventFormatter::with_schema`],
+/// span fields are written as configured with [`EventFormatter::with_spans`].
 /// Example:
 /// ```
 /// use fregate::observability::EventFormatter;
@@ -77,6 +86,10 @@ const MIN_LOG_MESSAGE_LEN: usize = 256;
 pub struct EventFormatter {
     additional_fields: BTreeMap<String, Value>,
     msg_len: Option<usize>,
+    format: LogFormat,
+    schema: LogSchema,
+    spans: LogSpans,
+    syslog_facility: Option<u8>,
 }
 
 impl EventFormatter {
@@ -90,11 +103,50 @@ impl EventFormatter {
         Self {
             additional_fields: Default::default(),
             msg_len,
+            format: LogFormat::Json,
+            schema: LogSchema::default(),
+            spans: LogSpans::default(),
+            syslog_facility: None,
+        }
+    }
+
+    /// Sets output format, [`LogFormat::Json`] by default.
+    #[must_use]
+    pub fn with_format(self, format: LogFormat) -> Self {
+        Self { format, ..self }
+    }
+
+    /// Sets keys and level values of JSON, [`LogSchema::default`] by default.\
+    /// Should be called before [`EventFormatter::add_field_to_events`] as added keys are checked against schema.
+    #[must_use]
+    pub fn with_schema(self, schema: LogSchema) -> Self {
+        Self { schema, ..self }
+    }
+
+    /// Returns keys and level values of JSON.
+    pub fn schema(&self) -> &LogSchema {
+        &self.schema
+    }
+
+    /// Sets span fields and names written with each event, none by default.\
+    /// Span fields are recorded by [`crate::observability::SpanFieldsLayer`] which should be added next to formatting layer.
+    /// Should be called before [`EventFormatter::add_field_to_events`] as span names key is reserved only if names are written.
+    #[must_use]
+    pub fn with_spans(self, spans: LogSpans) -> Self {
+        Self { spans, ..self }
+    }
+
+    /// Prefixes each line with syslog `<PRI>` priority computed from given facility and event level, see [`crate::observability::SyslogWriter`].
+    #[must_use]
+    pub fn with_syslog_facility(self, facility: u8) -> Self {
+        Self {
+            syslog_facility: Some(facility),
+            ..self
         }
     }
 
     /// add key-value pair to be printed in all events\
-    /// returns [`Error`] if one of possible keys are added:
+    /// returns [`Error`] if key is `message` or one of [`LogSchema`] keys, with default schema these are:
     /// ```rust
     /// pub(crate) const VERSION: &str = "version";
     /// pub(crate) const SERVICE: &str = "service";
@@ -109,7 +161,7 @@ impl EventFormatter {
     /// pub(crate) const SPAN_ID: &str = "spanId";
     /// ```
     pub fn add_field_to_events<V: Serialize>(&mut self, key: &str, value: V) -> Result<()> {
-        if DEFAULT_FIELDS.contains(&key) {
+        if self.is_reserved(key) {
             Err(Error::CustomError(format!(
                 "Prohibited to add key: '{key}' to EventFormatter"
             )))
@@ -127,6 +179,57 @@ impl EventFormatter {
         self.additional_fields.insert(key.to_owned(), val);
         Ok(())
     }
+
+    fn is_reserved(&self, key: &str) -> bool {
+        let unused_spans_key = !self.spans.names && self.schema.key(LogField::Spans) == Some(key);
+        key == MESSAGE || (self.schema.is_reserved(key) && !unused_spans_key)
+    }
+
+    /// Returns names of spans from root to current and fields of spans within [`LogSpans::depth`].
+    fn span_scope<S, N>(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+    ) -> (Option<Value>, BTreeMap<Cow<'static, str>, Value>)
+    where
+        S: Subscriber + for<'a> LookupSpan<'a>,
+        N: for<'a> FormatFields<'a> + 'static,
+    {
+        let names = self
+            .spans
+            .names
+            .then(|| ctx.event_scope())
+            .flatten()
+            .map(|scope| scope.from_root().map(|span| span.name().into()).collect());
+
+        let mut fields = BTreeMap::new();
+        if self.spans.records_fields() {
+            // Scope is iterated from current span to root.
+            for span in ctx
+                .event_scope()
+                .into_iter()
+                .flatten()
+                .take(self.spans.depth())
+            {
+                let extensions = span.extensions();
+                let Some(SpanFieldValues(values)) = extensions.get::<SpanFieldValues>() else {
+                    continue;
+                };
+
+                for (key, value) in values {
+                    match self.spans.conflict {
+                        SpanConflict::Inner => {
+                            fields.entry(key.clone()).or_insert_with(|| value.clone());
+                        }
+                        SpanConflict::Outer => {
+                            fields.insert(key.clone(), value.clone());
+                        }
+                    }
+                }
+            }
+        }
+
+        (names.map(Value::Array), fields)
+    }
 }
 
 impl<S, N> FormatEvent<S, N> for EventFormatter
@@ -140,87 +243,144 @@ where
         mut writer: format::Writer<'_>,
         event: &Event<'_>,
     ) -> fmt::Result {
+        let mut visitor = JsonVisitor::new();
+        event.record(&mut visitor);
+        let mut event_storage = visitor.storage;
+
+        let message = event_storage
+            .remove(MESSAGE)
+            .map(|mut msg| {
+                if let Some(limit) = self.msg_len {
+                    limit_str_value(&mut msg, limit);
+                }
+                msg
+            })
+            .unwrap_or_default();
+        let event_fields = event_storage.iter().filter(|(key, _)| {
+            !self.is_reserved(key.as_ref()) && !self.additional_fields.contains_key(key.as_ref())
+        });
+        let (span_names, span_fields) = self.span_scope(ctx);
+        let span_fields = span_fields.iter().filter(|(key, _)| {
+            !self.is_reserved(key.as_ref())
+                && !self.additional_fields.contains_key(key.as_ref())
+                && !event_storage.contains_key(key.as_ref())
+        });
+        let target = event.metadata().target();
+        let level = event.metadata().level();
+        if let Some(facility) = self.syslog_facility {
+            write!(writer, "<{}>", priority(facility, level))?;
+        }
+        let time = time::OffsetDateTime::now_utc();
+        let timestamp = time.format(
+            &Iso8601::<
+                {
+                    Config::DEFAULT
+                        .set_time_precision(TimePrecision::Second {
+                            decimal_digits: NonZeroU8::new(3),
+                        })
+                        .encode()
+                },
+            >,
+        );
+        let tracing_fields = ctx
+            .lookup_current()
+            .as_ref()
+            .map(SpanRef::extensions)
+            .as_ref()
+            .and_then(Extensions::get::<OtelData>)
+            .and_then(|otel_data| {
+                if otel_data.parent_cx.has_active_span() {
+                    Some(otel_data.parent_cx.span().span_context().trace_id())
+                } else {
+                    otel_data.builder.trace_id
+                }
+                .map(|trace_id| {
+                    let span_id = otel_data.builder.span_id.unwrap_or(SpanId::INVALID);
+                    (span_id, trace_id)
+                })
+            });
+
+        let schema = &self.schema;
+        let ids = tracing_fields.map(|(span_id, trace_id)| {
+            [
+                (
+                    schema.key(LogField::TraceId),
+                    Value::String(schema.trace_id(trace_id)),
+                ),
+                (
+                    schema.key(LogField::SpanId),
+                    Value::String(schema.span_id(span_id)),
+                ),
+            ]
+        });
+        let span_names = span_names.map(|names| (schema.key(LogField::Spans), names));
+        let ids = ids
+            .iter()
+            .flatten()
+            .chain(span_names.iter())
+            .filter_map(|(key, value)| key.map(|key| (key, value)));
+
+        if self.format != LogFormat::Json {
+            let fields = ids
+                .chain(self.additional_fields.iter().map(|(k, v)| (k.as_str(), v)))
+                .chain(event_fields.map(|(k, v)| (k.as_ref(), v)))
+                .chain(span_fields.map(|(k, v)| (k.as_ref(), v)));
+
+            let text = TextEvent {
+                timestamp: timestamp.ok(),
+                level: *level,
+                level_value: schema.level(level),
+                target,
+                message: &message,
+            };
+            text.write(&mut writer, self.format, fields)?;
+            return writeln!(writer);
+        }
+
         let serialize = || {
             let mut buf = Vec::with_capacity(MIN_LOG_MESSAGE_LEN);
             let mut serializer = serde_json::Serializer::new(&mut buf);
             let mut map_fmt = serializer.serialize_map(None)?;
 
-            let mut visitor = JsonVisitor::new();
-            event.record(&mut visitor);
-            let mut event_storage = visitor.storage;
-
-            let message = event_storage
-                .remove(MESSAGE)
-                .map(|mut msg| {
-                    if let Some(limit) = self.msg_len {
-                        limit_str_value(&mut msg, limit);
-                    }
-                    msg
-                })
-                .unwrap_or_default();
-            let mut event_fields = event_storage.iter().filter(|(key, _)| {
-                !DEFAULT_FIELDS.contains(&key.as_ref())
-                    && !self.additional_fields.contains_key(key.as_ref())
-            });
+            let mut event_fields = event_fields;
+            let mut span_fields = span_fields;
             let mut additional_fields = self.additional_fields.iter();
-            let target = event.metadata().target();
-            let level = event.metadata().level();
-            let time = time::OffsetDateTime::now_utc();
+            let mut ids = ids;
             let time_ns = time.unix_timestamp_nanos();
-            let timestamp = time.format(
-                &Iso8601::<
-                    {
-                        Config::DEFAULT
-                            .set_time_precision(TimePrecision::Second {
-                                decimal_digits: NonZeroU8::new(3),
-                            })
-                            .encode()
-                    },
-                >,
-            );
-            let tracing_fields = ctx
-                .lookup_current()
-                .as_ref()
-                .map(SpanRef::extensions)
-                .as_ref()
-                .and_then(Extensions::get::<OtelData>)
-                .and_then(|otel_data| {
-                    if otel_data.parent_cx.has_active_span() {
-                        Some(otel_data.parent_cx.span().span_context().trace_id())
-                    } else {
-                        otel_data.builder.trace_id
-                    }
-                    .map(|trace_id| {
-                        let span_id = otel_data.builder.span_id.unwrap_or(SpanId::INVALID);
-                        (span_id, trace_id)
-                    })
-                });
 
             // serialize time
-            map_fmt.serialize_entry(TIME, &time_ns)?;
-            if let Ok(timestamp) = timestamp {
-                map_fmt.serialize_entry(TIMESTAMP, timestamp.as_str())?;
+            if let Some(key) = schema.key(LogField::Time) {
+                map_fmt.serialize_entry(key, &time_ns)?;
+            }
+            if let (Some(key), Ok(timestamp)) = (schema.key(LogField::Timestamp), &timestamp) {
+                map_fmt.serialize_entry(key, timestamp.as_str())?;
             }
 
             // serialize event metadata
-            map_fmt.serialize_entry(LOG_LEVEL, level.as_str())?;
-            map_fmt.serialize_entry(TARGET, target)?;
-
-            // If event under span serialize traceId and spanId
-            if let Some((span_id, trace_id)) = tracing_fields {
-                map_fmt.serialize_entry(TRACE_ID, &trace_id.to_string())?;
-                map_fmt.serialize_entry(SPAN_ID, &span_id.to_string())?;
+            if let Some(key) = schema.key(LogField::Level) {
+                map_fmt.serialize_entry(key, schema.level(level))?;
             }
+            if let Some(key) = schema.key(LogField::Target) {
+                map_fmt.serialize_entry(key, target)?;
+            }
+
+            // If event under span serialize traceId, spanId and span names
+            ids.try_for_each(|(k, v)| map_fmt.serialize_entry(k, v))?;
 
             // serialize additional fields
             additional_fields.try_for_each(|(k, v)| map_fmt.serialize_entry(k, v))?;
 
             // Limit msg field
-            map_fmt.serialize_entry(MSG, &message)?;
+            if let Some(key) = schema.key(LogField::Msg) {
+                map_fmt.serialize_entry(key, &message)?;
+            }
 
             // serialize event fields
             event_fields.try_for_each(|(k, v)| map_fmt.serialize_entry(k, v))?;
 
+            // serialize span fields not overridden by event
+            span_fields.try_for_each(|(k, v)| map_fmt.serialize_entry(k, v))?;
+
             map_fmt.end()?;
             Ok(buf)
         };
@@ -245,13 +405,99 @@ where
     }
 }
 
+/// Event rendered by [`LogFormat::Pretty`] and [`LogFormat::Compact`].
+struct TextEvent<'a> {
+    timestamp: Option<String>,
+    level: Level,
+    level_value: &'a str,
+    target: &'a str,
+    message: &'a Value,
+}
+
+impl TextEvent<'_> {
+    fn write<'f>(
+        &self,
+        writer: &mut format::Writer<'_>,
+        format: LogFormat,
+        fields: impl Iterator<Item = (&'f str, &'f Value)>,
+    ) -> fmt::Result {
+        let ansi = writer.has_ansi_escapes();
+        let paint = |writer: &mut format::Writer<'_>, style: &str, text: &dyn fmt::Display| {
+            if ansi {
+                write!(writer, "{style}{text}{ANSI_RESET}")
+            } else {
+                write!(writer, "{text}")
+            }
+        };
+
+        if let Some(timestamp) = &self.timestamp {
+            paint(writer, ANSI_DIMMED, timestamp)?;
+            write!(writer, " ")?;
+        }
+        paint(
+            writer,
+            level_style(self.level),
+            &format_args!("{:>5}", self.level_value),
+        )?;
+        write!(writer, " ")?;
+        paint(writer, ANSI_DIMMED, &format_args!("{}:", self.target))?;
+        write!(writer, " ")?;
+        paint(writer, ANSI_BOLD, &TextValue(self.message))?;
+
+        for (key, value) in fields {
+            match format {
+                LogFormat::Pretty => {
+                    writeln!(writer)?;
+                    write!(writer, "{PRETTY_INDENT}")?;
+                    paint(writer, ANSI_DIMMED, &format_args!("{key}:"))?;
+                    write!(writer, " {}", TextValue(value))?;
+                }
+                LogFormat::Compact | LogFormat::Json => {
+                    write!(writer, " ")?;
+                    paint(writer, ANSI_DIMMED, &format_args!("{key}="))?;
+                    write!(writer, "{}", TextValue(value))?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn level_style(level: Level) -> &'static str {
+    match level {
+        Level::ERROR => "\x1b[31m",
+        Level::WARN => "\x1b[33m",
+        Level::INFO => "\x1b[32m",
+        Level::DEBUG => "\x1b[34m",
+        Level::TRACE => "\x1b[35m",
+    }
+}
+
+/// Writes strings without quotes unless they are empty or contain whitespace or `=`, other values as JSON.
+struct TextValue<'a>(&'a Value);
+
+impl fmt::Display for TextValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Value::String(str)
+                if !str.is_empty()
+                    && !str.contains(|char: char| char.is_whitespace() || char == '=') =>
+            {
+                write!(f, "{str}")
+            }
+            value => write!(f, "{value}"),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default)]
-struct JsonVisitor<'a> {
-    storage: BTreeMap<Cow<'a, str>, Value>,
+pub(crate) struct JsonVisitor<'a> {
+    pub(crate) storage: BTreeMap<Cow<'a, str>, Value>,
 }
 
 impl<'a> JsonVisitor<'a> {
-    fn new() -> Self {
+    pub(crate) fn new() -> Self {
         Self {
             storage: Default::default(),
         }
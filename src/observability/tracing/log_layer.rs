@@ -1,5 +1,8 @@
+use crate::configuration::{LogFormat, LogOutput, ObservabilityConfig};
 use crate::error::Result;
-use crate::observability::tracing::{event_formatter::EventFormatter, COMPONENT, SERVICE, VERSION};
+use crate::observability::tracing::event_formatter::EventFormatter;
+use crate::observability::{LogField, RollingFileWriter, SpanFieldsLayer, SyslogWriter};
+use std::io::{IsTerminal, Write};
 use std::str::FromStr;
 use tracing::Subscriber;
 use tracing_appender::non_blocking::{WorkerGuard, DEFAULT_BUFFERED_LINES_LIMIT};
@@ -8,7 +11,8 @@ use tracing_subscriber::{
 };
 
 /// Returns [`Layer`] with custom event formatter [`EventFormatter`]
-/// Configured with non-blocking writer [`tracing_appender::non_blocking::NonBlocking`] to [`std::io::stdout()`]
+/// Configured with non-blocking writer [`tracing_appender::non_blocking::NonBlocking`] to [`std::io::stdout()`]\
+/// See [`log_layer_with_config`] to set log output, format and schema.
 #[allow(clippy::type_complexity)]
 pub fn log_layer<S>(
     log_level: &str,
@@ -25,23 +29,74 @@ pub fn log_layer<S>(
 where
     S: Subscriber + for<'a> LookupSpan<'a>,
 {
-    let mut formatter = EventFormatter::new_with_limit(log_msg_length);
+    let config = ObservabilityConfig {
+        version: version.to_owned(),
+        service_name: service_name.to_owned(),
+        component_name: component_name.to_owned(),
+        msg_length: log_msg_length,
+        buffered_lines_limit,
+        ..Default::default()
+    };
 
-    formatter.add_default_field_to_events(VERSION, version)?;
-    formatter.add_default_field_to_events(SERVICE, service_name)?;
-    formatter.add_default_field_to_events(COMPONENT, component_name)?;
+    log_layer_with_config(&config, log_level, &LogOutput::Stdout, LogFormat::Json)
+}
+
+/// Returns [`Layer`] with custom event formatter [`EventFormatter`]
+/// Configured with non-blocking writer [`tracing_appender::non_blocking::NonBlocking`] to [`std::io::stdout()`], [`std::io::stderr()`], [`RollingFileWriter`] or [`SyslogWriter`] depending on [`LogOutput`]\
+/// Level, output and format are given separately as they differ for each of [`ObservabilityConfig::log_sinks`].\
+/// [`LogFormat::Pretty`] and [`LogFormat::Compact`] are colored only if stdout or stderr is a terminal.\
+/// Version, service and component are written with keys of [`LogSchema`](crate::observability::LogSchema) unless schema omits them.\
+/// Span fields are recorded with [`SpanFieldsLayer`] under the same filter if [`LogSpans`](crate::observability::LogSpans) writes them.
+#[allow(clippy::type_complexity)]
+pub fn log_layer_with_config<S>(
+    config: &ObservabilityConfig,
+    log_level: &str,
+    log_output: &LogOutput,
+    log_format: LogFormat,
+) -> Result<(
+    Filtered<Box<dyn Layer<S> + Send + Sync>, reload::Layer<EnvFilter, S>, S>,
+    Handle<EnvFilter, S>,
+    WorkerGuard,
+)>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    let mut formatter = EventFormatter::new_with_limit(config.msg_length)
+        .with_format(log_format)
+        .with_schema(config.log_schema.clone())
+        .with_spans(config.log_spans.clone());
+    if let LogOutput::Syslog(syslog) = log_output {
+        formatter = formatter.with_syslog_facility(syslog.facility);
+    }
+
+    for (field, value) in [
+        (LogField::Version, &config.version),
+        (LogField::Service, &config.service_name),
+        (LogField::Component, &config.component_name),
+    ] {
+        if let Some(key) = config.log_schema.key(field) {
+            formatter.add_default_field_to_events(key, value)?;
+        }
+    }
 
-    let buffered_lines_limit = buffered_lines_limit.unwrap_or(DEFAULT_BUFFERED_LINES_LIMIT);
+    let buffered_lines_limit = config
+        .buffered_lines_limit
+        .unwrap_or(DEFAULT_BUFFERED_LINES_LIMIT);
 
     let (writer, guard) = tracing_appender::non_blocking::NonBlockingBuilder::default()
         .lossy(false)
         .buffered_lines_limit(buffered_lines_limit)
-        .finish(std::io::stdout());
+        .finish(log_writer(log_output)?);
 
     let layer = tracing_subscriber::fmt::layer()
         .with_writer(writer)
-        .event_format(formatter)
-        .boxed();
+        .with_ansi(is_terminal(log_output))
+        .event_format(formatter);
+    let layer = if config.log_spans.records_fields() {
+        layer.and_then(SpanFieldsLayer).boxed()
+    } else {
+        layer.boxed()
+    };
 
     let filter = EnvFilter::from_str(log_level).unwrap_or_default();
     let (filter, reload) = reload::Layer::new(filter);
@@ -49,3 +104,20 @@ where
 
     Ok((layer, reload, guard))
 }
+
+fn log_writer(log_output: &LogOutput) -> Result<Box<dyn Write + Send>> {
+    Ok(match log_output {
+        LogOutput::Stdout => Box::new(std::io::stdout()),
+        LogOutput::Stderr => Box::new(std::io::stderr()),
+        LogOutput::File(config) => Box::new(RollingFileWriter::new(config.clone())?),
+        LogOutput::Syslog(syslog) => Box::new(SyslogWriter::new(syslog.path.clone())?),
+    })
+}
+
+fn is_terminal(log_output: &LogOutput) -> bool {
+    match log_output {
+        LogOutput::Stdout => std::io::stdout().is_terminal(),
+        LogOutput::Stderr => std::io::stderr().is_terminal(),
+        LogOutput::File(_) | LogOutput::Syslog(_) => false,
+    }
+}
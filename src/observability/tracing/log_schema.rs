@@ -0,0 +1,429 @@
+use crate::extensions::DeserializeExt;
+use crate::observability::tracing::event_formatter::{
+    COMPONENT, LOG_LEVEL, MSG, SERVICE, SPANS, SPAN_ID, TARGET, TIME, TIMESTAMP, TRACE_ID, VERSION,
+};
+use crate::ConfigSchema;
+use opentelemetry::trace::{SpanId, TraceId};
+use serde::de::Error;
+use serde::{Deserialize, Deserializer};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use tracing::Level;
+
+const SCHEMA_PTR: &str = "/schema";
+const KEYS_PTR: &str = "/keys";
+const LEVELS_PTR: &str = "/levels";
+const GCP_PROJECT_ID_PTR: &str = "/gcp/project_id";
+
+/// Field written by [`crate::observability::EventFormatter`] whose key is defined by [`LogSchema`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LogField {
+    /// unix time in nanoseconds, `time` by default
+    Time,
+    /// ISO 8601 time, `timestamp` by default
+    Timestamp,
+    /// event level, `LogLevel` by default
+    Level,
+    /// event target, `target` by default
+    Target,
+    /// event message, `msg` by default
+    Msg,
+    /// trace id of current span, `traceId` by default
+    TraceId,
+    /// id of current span, `spanId` by default
+    SpanId,
+    /// component version, `version` by default
+    Version,
+    /// service name, `service` by default
+    Service,
+    /// component name, `component` by default
+    Component,
+    /// names of spans from root to current, `spans` by default, written if [`crate::observability::LogSpans::names`] is set
+    Spans,
+}
+
+impl LogField {
+    const ALL: [LogField; 11] = [
+        LogField::Time,
+        LogField::Timestamp,
+        LogField::Level,
+        LogField::Target,
+        LogField::Msg,
+        LogField::TraceId,
+        LogField::SpanId,
+        LogField::Version,
+        LogField::Service,
+        LogField::Component,
+        LogField::Spans,
+    ];
+
+    /// Name of field in `log.keys` configuration.
+    fn config_name(self) -> &'static str {
+        match self {
+            LogField::Time => "time",
+            LogField::Timestamp => "timestamp",
+            LogField::Level => "level",
+            LogField::Target => "target",
+            LogField::Msg => "msg",
+            LogField::TraceId => "trace",
+            LogField::SpanId => "span",
+            LogField::Version => "version",
+            LogField::Service => "service",
+            LogField::Component => "component",
+            LogField::Spans => "spans",
+        }
+    }
+}
+
+/// How trace and span ids are written.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IdFormat {
+    /// lowercase hex, as in W3C trace context
+    #[default]
+    Hex,
+    /// decimal of lower 64 bits, as expected by Datadog
+    Decimal,
+}
+
+/// Keys and level values of log lines, text formats use only level values and trace and span id keys.\
+/// Starts from one of presets and might be adjusted per field:
+/// ```
+/// use fregate::observability::{LogField, LogSchema};
+/// use fregate::tracing::Level;
+///
+/// let schema = LogSchema::gcp()
+///     .rename(LogField::Target, "logger")
+///     .omit(LogField::Component)
+///     .map_level(Level::TRACE, "DEFAULT");
+///
+/// assert_eq!(schema.key(LogField::Level), Some("severity"));
+/// assert_eq!(schema.level(&Level::WARN), "WARNING");
+/// ```
+/// Read from configuration:
+/// ```toml
+/// [log]
+/// schema = "gcp"    # "default", "ecs", "gcp" or "datadog"
+///
+/// [log.keys]        # time, timestamp, level, target, msg, trace, span, version, service, component, spans
+/// target = "logger" # empty string omits field
+///
+/// [log.levels]      # trace, debug, info, warn, error
+/// trace = "DEFAULT"
+///
+/// [log.gcp]
+/// project_id = "my-project" # trace is written as `projects/my-project/traces/{trace_id}`
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogSchema {
+    keys: BTreeMap<LogField, String>,
+    levels: BTreeMap<Level, String>,
+    ids: IdFormat,
+    trace_prefix: String,
+}
+
+impl Default for LogSchema {
+    fn default() -> Self {
+        Self::with_keys([
+            (LogField::Time, TIME),
+            (LogField::Timestamp, TIMESTAMP),
+            (LogField::Level, LOG_LEVEL),
+            (LogField::Target, TARGET),
+            (LogField::Msg, MSG),
+            (LogField::TraceId, TRACE_ID),
+            (LogField::SpanId, SPAN_ID),
+            (LogField::Version, VERSION),
+            (LogField::Service, SERVICE),
+            (LogField::Component, COMPONENT),
+            (LogField::Spans, SPANS),
+        ])
+    }
+}
+
+impl LogSchema {
+    fn with_keys<const N: usize>(keys: [(LogField, &str); N]) -> Self {
+        Self {
+            keys: keys
+                .into_iter()
+                .map(|(field, key)| (field, key.to_owned()))
+                .collect(),
+            levels: BTreeMap::new(),
+            ids: IdFormat::Hex,
+            trace_prefix: String::new(),
+        }
+    }
+
+    /// [Elastic Common Schema](https://www.elastic.co/guide/en/ecs/current/ecs-field-reference.html), unix time is omitted.
+    pub fn ecs() -> Self {
+        Self::with_keys([
+            (LogField::Timestamp, "@timestamp"),
+            (LogField::Level, "log.level"),
+            (LogField::Target, "log.logger"),
+            (LogField::Msg, "message"),
+            (LogField::TraceId, "trace.id"),
+            (LogField::SpanId, "span.id"),
+            (LogField::Version, "service.version"),
+            (LogField::Service, "service.name"),
+            (LogField::Component, "service.node.name"),
+            (LogField::Spans, "spans"),
+        ])
+    }
+
+    /// [Google Cloud Logging](https://cloud.google.com/logging/docs/structured-logging) with `WARNING` and `DEFAULT` severities, unix time is omitted.\
+    /// Trace is linked to Cloud Trace only if project is set with [`LogSchema::with_gcp_project`].
+    pub fn gcp() -> Self {
+        Self::with_keys([
+            (LogField::Timestamp, "time"),
+            (LogField::Level, "severity"),
+            (LogField::Target, "target"),
+            (LogField::Msg, "message"),
+            (LogField::TraceId, "logging.googleapis.com/trace"),
+            (LogField::SpanId, "logging.googleapis.com/spanId"),
+            (LogField::Version, "version"),
+            (LogField::Service, "service"),
+            (LogField::Component, "component"),
+            (LogField::Spans, "spans"),
+        ])
+        .map_level(Level::WARN, "WARNING")
+        .map_level(Level::TRACE, "DEFAULT")
+    }
+
+    /// [Datadog](https://docs.datadoghq.com/logs/log_configuration/attributes_naming_convention/) with decimal trace and span ids, unix time is omitted.
+    pub fn datadog() -> Self {
+        Self {
+            ids: IdFormat::Decimal,
+            ..Self::with_keys([
+                (LogField::Timestamp, "timestamp"),
+                (LogField::Level, "status"),
+                (LogField::Target, "logger.name"),
+                (LogField::Msg, "message"),
+                (LogField::TraceId, "dd.trace_id"),
+                (LogField::SpanId, "dd.span_id"),
+                (LogField::Version, "dd.version"),
+                (LogField::Service, "dd.service"),
+                (LogField::Component, "component"),
+                (LogField::Spans, "spans"),
+            ])
+        }
+    }
+
+    /// Returns preset by name: `default`, `ecs`, `gcp` or `datadog`.
+    pub fn preset(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "default" => Some(Self::default()),
+            "ecs" => Some(Self::ecs()),
+            "gcp" => Some(Self::gcp()),
+            "datadog" => Some(Self::datadog()),
+            _ => None,
+        }
+    }
+
+    /// Writes field with given key.
+    #[must_use]
+    pub fn rename(mut self, field: LogField, key: impl Into<String>) -> Self {
+        self.keys.insert(field, key.into());
+        self
+    }
+
+    /// Does not write field.
+    #[must_use]
+    pub fn omit(mut self, field: LogField) -> Self {
+        self.keys.remove(&field);
+        self
+    }
+
+    /// Writes given value instead of level name.
+    #[must_use]
+    pub fn map_level(mut self, level: Level, value: impl Into<String>) -> Self {
+        self.levels.insert(level, value.into());
+        self
+    }
+
+    /// Sets how trace and span ids are written.
+    #[must_use]
+    pub fn with_ids(self, ids: IdFormat) -> Self {
+        Self { ids, ..self }
+    }
+
+    /// Writes trace id as `projects/{project_id}/traces/{trace_id}`, as expected by Google Cloud Logging.
+    #[must_use]
+    pub fn with_gcp_project(self, project_id: &str) -> Self {
+        Self {
+            trace_prefix: format!("projects/{project_id}/traces/"),
+            ..self
+        }
+    }
+
+    /// Returns key of field or [`None`] if it is omitted.
+    pub fn key(&self, field: LogField) -> Option<&str> {
+        self.keys.get(&field).map(String::as_str)
+    }
+
+    /// Returns value written for level.
+    pub fn level<'a>(&'a self, level: &'a Level) -> &'a str {
+        self.levels
+            .get(level)
+            .map_or_else(|| level.as_str(), String::as_str)
+    }
+
+    /// Returns [`true`] if key is used by one of schema fields.
+    pub fn is_reserved(&self, key: &str) -> bool {
+        self.keys.values().any(|reserved| reserved == key)
+    }
+
+    pub(crate) fn trace_id(&self, trace_id: TraceId) -> String {
+        let trace_id = match self.ids {
+            IdFormat::Hex => trace_id.to_string(),
+            IdFormat::Decimal => {
+                let lower = u128::from_be_bytes(trace_id.to_bytes()) as u64;
+                lower.to_string()
+            }
+        };
+
+        format!("{}{trace_id}", self.trace_prefix)
+    }
+
+    pub(crate) fn span_id(&self, span_id: SpanId) -> String {
+        match self.ids {
+            IdFormat::Hex => span_id.to_string(),
+            IdFormat::Decimal => u64::from_be_bytes(span_id.to_bytes()).to_string(),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for LogSchema {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let config = Value::deserialize(deserializer)?;
+
+        let mut schema = match config.pointer_and_deserialize::<String, D::Error>(SCHEMA_PTR) {
+            Ok(name) => LogSchema::preset(&name).ok_or_else(|| {
+                D::Error::custom(format!(
+                    "unknown log schema `{name}`, expected `default`, `ecs`, `gcp` or `datadog`"
+                ))
+            })?,
+            Err(_) => LogSchema::default(),
+        };
+
+        let keys = config
+            .pointer_and_deserialize::<Map<String, Value>, D::Error>(KEYS_PTR)
+            .unwrap_or_default();
+        for (name, key) in keys {
+            let field = LogField::ALL
+                .into_iter()
+                .find(|field| field.config_name() == name.to_lowercase())
+                .ok_or_else(|| D::Error::custom(format!("unknown log field `{name}`")))?;
+            let key = String::deserialize(key).map_err(D::Error::custom)?;
+
+            schema = if key.is_empty() {
+                schema.omit(field)
+            } else {
+                schema.rename(field, key)
+            };
+        }
+
+        let levels = config
+            .pointer_and_deserialize::<Map<String, Value>, D::Error>(LEVELS_PTR)
+            .unwrap_or_default();
+        for (name, value) in levels {
+            let level = Level::from_str(&name)
+                .map_err(|_| D::Error::custom(format!("unknown log level `{name}`")))?;
+            let value = String::deserialize(value).map_err(D::Error::custom)?;
+            schema = schema.map_level(level, value);
+        }
+
+        if let Some(project_id) = config
+            .pointer(GCP_PROJECT_ID_PTR)
+            .map(String::deserialize)
+            .transpose()
+            .map_err(D::Error::custom)?
+        {
+            schema = schema.with_gcp_project(&project_id);
+        }
+
+        Ok(schema)
+    }
+}
+
+impl ConfigSchema for LogSchema {
+    fn schema() -> Value {
+        let keys = LogField::ALL
+            .iter()
+            .map(|field| (field.config_name().to_owned(), json!({ "type": "string" })))
+            .collect::<Map<_, _>>();
+
+        json!({
+            "type": "object",
+            "properties": {
+                "schema": {
+                    "type": "string",
+                    "enum": ["default", "ecs", "gcp", "datadog"],
+                    "description": "preset of JSON keys and level values"
+                },
+                "keys": {
+                    "type": "object",
+                    "description": "JSON key of each field, empty string omits field",
+                    "properties": keys
+                },
+                "levels": {
+                    "type": "object",
+                    "description": "value written for each level, e.g. `warn = \"WARNING\"`",
+                    "properties": {
+                        "trace": { "type": "string" },
+                        "debug": { "type": "string" },
+                        "info": { "type": "string" },
+                        "warn": { "type": "string" },
+                        "error": { "type": "string" }
+                    }
+                },
+                "gcp": {
+                    "type": "object",
+                    "properties": {
+                        "project_id": {
+                            "type": "string",
+                            "description": "Google Cloud project, trace is written as `projects/{project_id}/traces/{trace_id}`",
+                            "examples": ["my-project"]
+                        }
+                    }
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod log_schema_test {
+    use super::*;
+
+    #[test]
+    fn decimal_ids_test() {
+        let schema = LogSchema::datadog();
+        let trace_id = TraceId::from_hex("0af7651916cd43dd8448eb211c80319c").unwrap();
+        let span_id = SpanId::from_hex("b7ad6b7169203331").unwrap();
+
+        assert_eq!(schema.trace_id(trace_id), "9532127138774266268");
+        assert_eq!(schema.span_id(span_id), "13235353014750950193");
+        assert_eq!(
+            LogSchema::default().trace_id(trace_id),
+            "0af7651916cd43dd8448eb211c80319c"
+        );
+    }
+
+    #[test]
+    fn gcp_trace_test() {
+        let trace_id = TraceId::from_hex("0af7651916cd43dd8448eb211c80319c").unwrap();
+        let schema = LogSchema::deserialize(
+            json!({ "schema": "gcp", "gcp": { "project_id": "my-project" } }),
+        )
+        .unwrap();
+
+        assert_eq!(schema, LogSchema::gcp().with_gcp_project("my-project"));
+        assert_eq!(
+            schema.trace_id(trace_id),
+            "projects/my-project/traces/0af7651916cd43dd8448eb211c80319c"
+        );
+    }
+}
@@ -0,0 +1,259 @@
+use crate::configuration::{LogFileConfig, LogRotation};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use time::OffsetDateTime;
+
+const GZIP_EXTENSION: &str = "gz";
+
+/// [`Write`] implementation appending to [`LogFileConfig::path`] and rotating it by [`LogFileConfig::rotation`].\
+/// Rotated file is renamed to `{path}.{period}` for time based rotation or `{path}.{timestamp}` for size based one,
+/// optionally gzipped, and only [`LogFileConfig::retain`] latest rotated files are kept.\
+/// Rotation happens on write, so it is expected to be wrapped into [`tracing_appender::non_blocking::NonBlocking`].
+#[derive(Debug)]
+pub struct RollingFileWriter {
+    config: LogFileConfig,
+    file: File,
+    size: u64,
+    period: Option<String>,
+}
+
+impl RollingFileWriter {
+    /// Opens or creates log file with its parent directories.
+    pub fn new(config: LogFileConfig) -> io::Result<Self> {
+        if let Some(dir) = config.path.parent() {
+            if !dir.as_os_str().is_empty() {
+                std::fs::create_dir_all(dir)?;
+            }
+        }
+
+        let file = open(&config.path)?;
+        let metadata = file.metadata()?;
+        let modified = metadata
+            .modified()
+            .map_or_else(|_| OffsetDateTime::now_utc(), OffsetDateTime::from);
+
+        Ok(Self {
+            period: period(config.rotation, modified),
+            size: metadata.len(),
+            file,
+            config,
+        })
+    }
+
+    fn rotate_if_needed(&mut self, len: usize) -> io::Result<()> {
+        let now = OffsetDateTime::now_utc();
+
+        let suffix = match self.config.rotation {
+            LogRotation::Never => None,
+            LogRotation::Hourly | LogRotation::Daily => {
+                let period = period(self.config.rotation, now);
+                if period != self.period {
+                    std::mem::replace(&mut self.period, period)
+                } else {
+                    None
+                }
+            }
+            LogRotation::Size(max) => (self.size > 0
+                && self.size.saturating_add(len as u64) > max as u64)
+                .then(|| timestamp(now)),
+        };
+
+        match suffix {
+            Some(suffix) => self.rotate(&suffix),
+            None => Ok(()),
+        }
+    }
+
+    fn rotate(&mut self, suffix: &str) -> io::Result<()> {
+        self.file.flush()?;
+
+        let rotated = rotated_path(&self.config.path, suffix);
+        std::fs::rename(&self.config.path, &rotated)?;
+        self.file = open(&self.config.path)?;
+        self.size = 0;
+
+        if self.config.compress {
+            compress(&rotated)?;
+        }
+        if let Some(retain) = self.config.retain {
+            remove_old(&self.config.path, retain)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Write for RollingFileWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.rotate_if_needed(buf.len())?;
+
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+fn open(path: &Path) -> io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+fn period(rotation: LogRotation, time: OffsetDateTime) -> Option<String> {
+    let date = format!(
+        "{:04}-{:02}-{:02}",
+        time.year(),
+        u8::from(time.month()),
+        time.day()
+    );
+
+    match rotation {
+        LogRotation::Daily => Some(date),
+        LogRotation::Hourly => Some(format!("{date}T{:02}", time.hour())),
+        LogRotation::Never | LogRotation::Size(_) => None,
+    }
+}
+
+fn timestamp(time: OffsetDateTime) -> String {
+    format!(
+        "{:04}-{:02}-{:02}T{:02}-{:02}-{:02}.{:03}",
+        time.year(),
+        u8::from(time.month()),
+        time.day(),
+        time.hour(),
+        time.minute(),
+        time.second(),
+        time.millisecond()
+    )
+}
+
+/// `{path}.{suffix}`, with `.{n}` appended if such file already exists.
+fn rotated_path(path: &Path, suffix: &str) -> PathBuf {
+    let base = format!("{}.{suffix}", path.display());
+
+    (0..)
+        .map(|n| match n {
+            0 => PathBuf::from(&base),
+            n => PathBuf::from(format!("{base}.{n}")),
+        })
+        .find(|path| !path.exists() && !gzip_path(path).exists())
+        .unwrap_or_else(|| PathBuf::from(base))
+}
+
+fn gzip_path(path: &Path) -> PathBuf {
+    let mut gzip_path = path.as_os_str().to_owned();
+    gzip_path.push(".");
+    gzip_path.push(GZIP_EXTENSION);
+    PathBuf::from(gzip_path)
+}
+
+fn compress(path: &Path) -> io::Result<()> {
+    let mut source = File::open(path)?;
+    let mut encoder = GzEncoder::new(File::create(gzip_path(path))?, Compression::default());
+    io::copy(&mut source, &mut encoder)?;
+    encoder.finish()?;
+
+    std::fs::remove_file(path)
+}
+
+/// Keeps `retain` most recently modified rotated files of `path`.
+fn remove_old(path: &Path, retain: usize) -> io::Result<()> {
+    let (Some(dir), Some(name)) = (path.parent(), path.file_name()) else {
+        return Ok(());
+    };
+    let dir = if dir.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        dir
+    };
+    let prefix = format!("{}.", name.to_string_lossy());
+
+    let mut rotated = std::fs::read_dir(dir)?
+        .flatten()
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with(&prefix))
+        .filter_map(|entry| {
+            let modified = entry.metadata().and_then(|meta| meta.modified()).ok()?;
+            Some((modified, entry.path()))
+        })
+        .collect::<Vec<_>>();
+
+    rotated.sort();
+
+    let remove = rotated.len().saturating_sub(retain);
+    for (_, path) in rotated.into_iter().take(remove) {
+        std::fs::remove_file(path)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod rolling_file_test {
+    use super::*;
+    use std::io::Read;
+
+    fn rotated_files(dir: &Path) -> Vec<PathBuf> {
+        let mut files = std::fs::read_dir(dir)
+            .unwrap()
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.file_name().unwrap() != "app.log")
+            .collect::<Vec<_>>();
+        files.sort();
+        files
+    }
+
+    #[test]
+    fn size_rotation_test() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        let mut writer = RollingFileWriter::new(LogFileConfig {
+            path: path.clone(),
+            rotation: LogRotation::Size(10),
+            retain: Some(2),
+            compress: true,
+        })
+        .unwrap();
+
+        for line in ["first\n", "second\n", "third\n", "fourth\n"] {
+            writer.write_all(line.as_bytes()).unwrap();
+        }
+        writer.flush().unwrap();
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "fourth\n");
+
+        let rotated = rotated_files(dir.path());
+        assert_eq!(rotated.len(), 2);
+
+        let mut decoded = String::new();
+        flate2::read::GzDecoder::new(File::open(rotated.get(1).unwrap()).unwrap())
+            .read_to_string(&mut decoded)
+            .unwrap();
+        assert!(rotated
+            .iter()
+            .all(|path| path.extension() == Some(GZIP_EXTENSION.as_ref())));
+        assert!(["second\n", "third\n"].contains(&decoded.as_str()));
+    }
+
+    #[test]
+    fn period_test() {
+        let time = OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap();
+
+        assert_eq!(
+            period(LogRotation::Daily, time).as_deref(),
+            Some("2023-11-14")
+        );
+        assert_eq!(
+            period(LogRotation::Hourly, time).as_deref(),
+            Some("2023-11-14T22")
+        );
+        assert_eq!(period(LogRotation::Size(1), time), None);
+        assert_eq!(timestamp(time), "2023-11-14T22-13-20.000");
+    }
+}
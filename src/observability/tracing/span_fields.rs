@@ -0,0 +1,207 @@
+use crate::extensions::DeserializeExt;
+use crate::observability::tracing::event_formatter::JsonVisitor;
+use crate::ConfigSchema;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer};
+use serde_json::{json, Value};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use tracing::span::{Attributes, Id, Record};
+use tracing::Subscriber;
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+const FIELDS_PTR: &str = "/fields";
+const DEPTH_PTR: &str = "/depth";
+const CONFLICT_PTR: &str = "/conflict";
+const NAMES_PTR: &str = "/names";
+
+/// Which span fields are written with each event, read from `log.spans`:
+/// ```toml
+/// [log.spans]
+/// fields = "scope"    # "none" (default), "current" or "scope"
+/// depth = 2           # with "scope", number of spans from current one, all if unset
+/// conflict = "inner"  # with "scope", field of "inner" (default) or "outer" span is kept
+/// names = true        # write names of spans from root to current
+/// ```
+/// Event fields, [`crate::observability::LogSchema`] keys and fields added with [`crate::observability::EventFormatter::add_field_to_events`] take precedence over span fields.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LogSpans {
+    /// spans whose fields are written
+    pub fields: SpanFieldsMode,
+    /// with [`SpanFieldsMode::Scope`], number of spans counting from current one, all if [`None`]
+    pub depth: Option<usize>,
+    /// with [`SpanFieldsMode::Scope`], which span wins if several of them have the same field
+    pub conflict: SpanConflict,
+    /// write names of spans from root to current as [`crate::observability::LogField::Spans`]
+    pub names: bool,
+}
+
+/// Spans whose fields are written with each event.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SpanFieldsMode {
+    /// span fields are not written
+    #[default]
+    None,
+    /// fields of current span
+    Current,
+    /// fields of every span from current to root
+    Scope,
+}
+
+/// Which span wins if several spans of scope have the same field.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SpanConflict {
+    /// span closest to event
+    #[default]
+    Inner,
+    /// span closest to root
+    Outer,
+}
+
+impl LogSpans {
+    /// Returns [`true`] if span fields are recorded by [`SpanFieldsLayer`].
+    pub fn records_fields(&self) -> bool {
+        self.fields != SpanFieldsMode::None
+    }
+
+    /// Returns number of spans from current one which fields are written.
+    pub(crate) fn depth(&self) -> usize {
+        match self.fields {
+            SpanFieldsMode::None => 0,
+            SpanFieldsMode::Current => 1,
+            SpanFieldsMode::Scope => self.depth.unwrap_or(usize::MAX),
+        }
+    }
+}
+
+/// Fields recorded by [`SpanFieldsLayer`] and kept in span extensions.
+#[derive(Debug, Default)]
+pub(crate) struct SpanFieldValues(pub(crate) BTreeMap<Cow<'static, str>, Value>);
+
+/// [`Layer`] recording span fields for [`crate::observability::EventFormatter`] configured with [`LogSpans`].\
+/// Should be added next to formatting layer so it records only spans enabled by the same filter:
+/// ```
+/// use fregate::observability::{EventFormatter, LogSpans, SpanFieldsLayer, SpanFieldsMode};
+/// use tracing_subscriber::{fmt::layer, layer::SubscriberExt, registry, util::SubscriberInitExt, Layer};
+///
+/// let spans = LogSpans {
+///     fields: SpanFieldsMode::Current,
+///     ..Default::default()
+/// };
+/// let layer = layer()
+///     .event_format(EventFormatter::new().with_spans(spans))
+///     .and_then(SpanFieldsLayer);
+/// registry().with(layer).init();
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SpanFieldsLayer;
+
+impl<S> Layer<S> for SpanFieldsLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut extensions = span.extensions_mut();
+
+        // Span might be already recorded by layer of another log sink.
+        if extensions.get_mut::<SpanFieldValues>().is_none() {
+            let mut visitor = JsonVisitor::new();
+            attrs.record(&mut visitor);
+            extensions.insert(SpanFieldValues(visitor.storage));
+        }
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut extensions = span.extensions_mut();
+
+        if let Some(SpanFieldValues(fields)) = extensions.get_mut::<SpanFieldValues>() {
+            let mut visitor = JsonVisitor::new();
+            values.record(&mut visitor);
+            fields.extend(visitor.storage);
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for LogSpans {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let config = Value::deserialize(deserializer)?;
+
+        let fields = match config
+            .pointer(FIELDS_PTR)
+            .map(String::deserialize)
+            .transpose()
+            .map_err(D::Error::custom)?
+            .map(|fields| fields.to_lowercase())
+            .as_deref()
+        {
+            None | Some("none") => SpanFieldsMode::None,
+            Some("current") => SpanFieldsMode::Current,
+            Some("scope") => SpanFieldsMode::Scope,
+            Some(fields) => {
+                return Err(D::Error::custom(format!(
+                    "unknown span fields `{fields}`, expected `none`, `current` or `scope`"
+                )))
+            }
+        };
+        let depth = config
+            .pointer(DEPTH_PTR)
+            .is_some()
+            .then(|| config.pointer_and_deserialize::<_, D::Error>(DEPTH_PTR))
+            .transpose()?;
+        let conflict = match config
+            .pointer(CONFLICT_PTR)
+            .map(String::deserialize)
+            .transpose()
+            .map_err(D::Error::custom)?
+            .map(|conflict| conflict.to_lowercase())
+            .as_deref()
+        {
+            None | Some("inner") => SpanConflict::Inner,
+            Some("outer") => SpanConflict::Outer,
+            Some(conflict) => {
+                return Err(D::Error::custom(format!(
+                    "unknown span conflict `{conflict}`, expected `inner` or `outer`"
+                )))
+            }
+        };
+        let names = config
+            .pointer(NAMES_PTR)
+            .is_some()
+            .then(|| config.pointer_and_deserialize::<_, D::Error>(NAMES_PTR))
+            .transpose()?
+            .unwrap_or_default();
+
+        Ok(LogSpans {
+            fields,
+            depth,
+            conflict,
+            names,
+        })
+    }
+}
+
+impl ConfigSchema for LogSpans {
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "description": "span fields written with each event",
+            "properties": {
+                "fields": { "type": "string", "enum": ["none", "current", "scope"], "default": "none" },
+                "depth": { "type": "integer", "minimum": 1, "description": "with `scope`, number of spans from current one, all if unset" },
+                "conflict": { "type": "string", "enum": ["inner", "outer"], "default": "inner", "description": "with `scope`, span whose field is kept" },
+                "names": { "type": "boolean", "default": false, "description": "write names of spans from root to current" }
+            }
+        })
+    }
+}
@@ -0,0 +1,105 @@
+use std::io::{self, Write};
+use std::path::PathBuf;
+use tracing::Level;
+
+/// Returns syslog priority of event: `facility * 8 + severity`, where severity is `err` (3), `warning` (4), `info` (6) or `debug` (7).
+pub(crate) fn priority(facility: u8, level: &Level) -> u8 {
+    let severity = match *level {
+        Level::ERROR => 3,
+        Level::WARN => 4,
+        Level::INFO => 6,
+        Level::DEBUG | Level::TRACE => 7,
+    };
+
+    facility.saturating_mul(8).saturating_add(severity)
+}
+
+/// [`Write`] implementation sending every log line as one datagram to local syslog Unix socket, usually `/dev/log`.\
+/// Lines are expected to start with `<PRI>` priority, [`crate::observability::EventFormatter::with_syslog_facility`] writes it from event level.\
+/// Socket is reconnected once if send fails, e.g. after syslog daemon restart.
+#[derive(Debug)]
+pub struct SyslogWriter {
+    path: PathBuf,
+    #[cfg(unix)]
+    socket: std::os::unix::net::UnixDatagram,
+}
+
+impl SyslogWriter {
+    /// Connects to syslog socket at given path.
+    #[cfg(unix)]
+    pub fn new(path: PathBuf) -> io::Result<Self> {
+        let socket = connect(&path)?;
+        Ok(Self { path, socket })
+    }
+
+    /// Unix sockets are not supported on this platform.
+    #[cfg(not(unix))]
+    pub fn new(path: PathBuf) -> io::Result<Self> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!(
+                "syslog socket `{}` is supported only on unix",
+                path.display()
+            ),
+        ))
+    }
+}
+
+#[cfg(unix)]
+fn connect(path: &std::path::Path) -> io::Result<std::os::unix::net::UnixDatagram> {
+    let socket = std::os::unix::net::UnixDatagram::unbound()?;
+    socket.connect(path)?;
+    Ok(socket)
+}
+
+impl Write for SyslogWriter {
+    #[cfg(unix)]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let line = buf.strip_suffix(b"\n").unwrap_or(buf);
+
+        if self.socket.send(line).is_err() {
+            self.socket = connect(&self.path)?;
+            self.socket.send(line)?;
+        }
+
+        Ok(buf.len())
+    }
+
+    #[cfg(not(unix))]
+    fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+        Err(io::ErrorKind::Unsupported.into())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(all(test, unix))]
+#[allow(clippy::unwrap_used)]
+mod syslog_test {
+    use super::*;
+    use std::os::unix::net::UnixDatagram;
+
+    #[test]
+    fn send_test() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("syslog.sock");
+        let server = UnixDatagram::bind(&path).unwrap();
+
+        let mut writer = SyslogWriter::new(path.clone()).unwrap();
+        writer.write_all(b"<14>{\"msg\":\"hello\"}\n").unwrap();
+
+        let mut buf = [0; 64];
+        let len = server.recv(&mut buf).unwrap();
+        assert_eq!(buf.get(..len), Some(b"<14>{\"msg\":\"hello\"}".as_slice()));
+    }
+
+    #[test]
+    fn priority_test() {
+        assert_eq!(priority(1, &Level::INFO), 14);
+        assert_eq!(priority(1, &Level::ERROR), 11);
+        assert_eq!(priority(16, &Level::WARN), 132);
+        assert_eq!(priority(23, &Level::TRACE), 191);
+    }
+}
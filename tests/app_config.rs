@@ -1,7 +1,11 @@
 mod app_config_tests {
     use config::FileFormat;
-    use fregate::{AppConfig, ConfigSource, Empty};
+    use fregate::error::Error;
+    use fregate::serde_json::{json, Value};
+    use fregate::{AppConfig, ConfigSource, Empty, StrictMode, Validate, ValidationErrors};
+    use std::collections::HashMap;
     use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+    use std::time::Duration;
 
     #[test]
     fn multiple_config() {
@@ -108,4 +112,608 @@ mod app_config_tests {
         assert_eq!(logger.trace_level, "debug".to_owned());
         assert_eq!(logger.log_level, "trace".to_owned());
     }
+
+    #[test]
+    fn management_auth() {
+        let config = AppConfig::<Empty>::load_from([ConfigSource::String(
+            r#"
+            [management.auth]
+            allow = "10.0.0.0/8, ::1"
+            [management.auth.bearer]
+            token = "secret"
+            [management.auth.exempt]
+            probes = true
+            "#,
+            FileFormat::Toml,
+        )])
+        .expect("Failed to build AppConfig");
+
+        let auth = config.management_cfg.auth;
+
+        assert!(auth.is_enabled());
+        assert_eq!(auth.bearer.as_deref(), Some("secret"));
+        assert!(auth.basic.is_none());
+        assert!(auth.exempt.probes);
+        assert!(!auth.exempt.metrics);
+        assert!(auth.is_allowed(IpAddr::V4(Ipv4Addr::new(10, 1, 2, 3))));
+        assert!(auth.is_allowed(IpAddr::V6(Ipv6Addr::LOCALHOST)));
+        assert!(!auth.is_allowed(IpAddr::V4(Ipv4Addr::new(11, 0, 0, 1))));
+        assert!(!format!("{auth:?}").contains("secret"));
+    }
+
+    #[test]
+    fn management_auth_invalid_allow_list() {
+        let config = AppConfig::<Empty>::load_from([ConfigSource::String(
+            r#"
+            [management.auth]
+            allow = "10.0.0.0/33"
+            "#,
+            FileFormat::Toml,
+        )]);
+
+        assert!(config.is_err());
+    }
+
+    #[test]
+    fn management_auth_empty_allow_list() {
+        let error = AppConfig::<Empty>::load_from([ConfigSource::String(
+            "[management.auth]\nallow = \" \"",
+            FileFormat::Toml,
+        )])
+        .unwrap_err();
+
+        assert!(
+            error.to_string().contains("/management/auth/allow"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn management_auth_invalid_exempt() {
+        let config = AppConfig::<Empty>::load_from([ConfigSource::String(
+            "[management.auth.exempt]\nprobes = \"yes please\"",
+            FileFormat::Toml,
+        )]);
+
+        assert!(config.is_err());
+    }
+
+    #[test]
+    fn management_auth_invalid_path() {
+        for section in ["bearer", "basic"] {
+            let config = AppConfig::<Empty>::load_from([ConfigSource::String(
+                &format!("[management.auth.{section}]\npath = [\"/run/secrets/token\"]"),
+                FileFormat::Toml,
+            )]);
+
+            assert!(config.is_err(), "{section}");
+        }
+    }
+
+    #[test]
+    fn management_endpoints_enabled() {
+        let config = AppConfig::<Empty>::load_from([ConfigSource::String(
+            r#"
+            [management]
+            prefix = "/_/"
+            [management.endpoints]
+            health = "/healthz"
+            metrics = { path = "/prometheus", enabled = false }
+            version = { enabled = false }
+            "#,
+            FileFormat::Toml,
+        )])
+        .expect("Failed to build AppConfig");
+
+        let mngmnt = config.management_cfg;
+
+        assert_eq!(mngmnt.prefix.as_ref().map(AsRef::as_ref), Some("/_/"));
+        assert_eq!(mngmnt.endpoints.health.as_ref(), "/healthz");
+        assert!(mngmnt.endpoints.health.is_enabled());
+        assert_eq!(mngmnt.endpoints.metrics.as_ref(), "/prometheus");
+        assert!(!mngmnt.endpoints.metrics.is_enabled());
+        assert_eq!(mngmnt.endpoints.version.as_ref(), "/version");
+        assert!(!mngmnt.endpoints.version.is_enabled());
+        assert!(mngmnt.endpoints.live.is_enabled());
+    }
+
+    #[test]
+    fn validation_errors() {
+        #[derive(Debug, serde::Deserialize)]
+        struct Private {
+            workers: usize,
+        }
+
+        impl Validate for Private {
+            fn validate(&self, errors: &mut ValidationErrors) {
+                if self.workers == 0 {
+                    errors.add("/workers", "must be greater than 0");
+                }
+            }
+        }
+
+        let error = AppConfig::<Private>::builder()
+            .add_default()
+            .add_str(
+                r#"
+                workers = 0
+                [log]
+                level = "app=loud"
+                [management.endpoints]
+                metrics = { path = "metrics" }
+                "#,
+                FileFormat::Toml,
+            )
+            .validate_private()
+            .build()
+            .expect_err("Invalid config must be rejected");
+
+        let Error::ValidationError(errors) = error else {
+            panic!("Unexpected error: {error}");
+        };
+
+        let pointers = errors
+            .errors()
+            .iter()
+            .map(|error| (error.pointer.as_str(), error.source.as_deref()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            pointers,
+            vec![
+                ("/log/level", Some("string")),
+                ("/management/endpoints/metrics", Some("string")),
+                ("/workers", Some("string")),
+            ]
+        );
+    }
+
+    #[test]
+    fn load_validated_from() {
+        #[derive(Debug, serde::Deserialize)]
+        struct Private {
+            workers: usize,
+        }
+
+        impl Validate for Private {
+            fn validate(&self, errors: &mut ValidationErrors) {
+                if self.workers == 0 {
+                    errors.add("/workers", "must be greater than 0");
+                }
+            }
+        }
+
+        let sources = |workers| [ConfigSource::Value(json!({ "workers": workers }))];
+
+        AppConfig::<Private>::load_from(sources(0)).expect("Private is not validated");
+        let error = AppConfig::<Private>::load_validated_from(sources(0))
+            .expect_err("Invalid private config must be rejected");
+        let Error::ValidationError(errors) = error else {
+            panic!("Unexpected error: {error}");
+        };
+        assert_eq!(errors.errors()[0].pointer, "/workers");
+
+        let config = AppConfig::<Private>::load_validated_from(sources(2)).unwrap();
+        assert_eq!(config.private.workers, 2);
+    }
+
+    #[test]
+    fn args() {
+        let config = AppConfig::<Empty>::builder()
+            .add_default()
+            .add_args([
+                "serve",
+                "--verbose",
+                "--port",
+                "9000",
+                "--output=json",
+                "--log.level=debug",
+                "--set",
+                "component.name=cli",
+                "--server.metrics.exemplars",
+            ])
+            .build()
+            .expect("Failed to build AppConfig");
+
+        assert_eq!(config.port, 9000);
+        assert_eq!(config.observability_cfg.log_level, "debug");
+        assert_eq!(config.observability_cfg.component_name, "cli");
+        assert!(config.observability_cfg.metrics_exemplars);
+        assert!(config.tree.get("verbose").is_none());
+        assert!(config.tree.get("output").is_none());
+
+        let config = AppConfig::<Empty>::builder()
+            .add_default()
+            .add_args(["--set", "port"])
+            .build();
+
+        assert!(config.is_err());
+    }
+
+    #[test]
+    fn args_applied_last() {
+        std::env::set_var("ARGS_LAST_PORT", "9001");
+
+        let config = AppConfig::<Empty>::builder()
+            .add_default()
+            .add_args(["--port", "9000", "--set", "component.name=cli"])
+            .add_env_prefixed("ARGS_LAST")
+            .add_file("./tests/resources/test_conf.toml")
+            .add_value(json!({ "port": 9002, "component": { "name": "value" } }))
+            .set_override("component.name", "override")
+            .unwrap()
+            .with_provenance()
+            .build()
+            .expect("Failed to build AppConfig");
+
+        assert_eq!(config.port, 9000);
+        assert_eq!(config.observability_cfg.component_name, "cli");
+
+        let provenance = config.explain().unwrap();
+        assert_eq!(
+            provenance.get("port").unwrap().source,
+            "command-line arguments"
+        );
+    }
+
+    #[test]
+    fn log_spans() {
+        let config = AppConfig::<Empty>::load_from([ConfigSource::Value(json!({
+            "log": { "spans": { "fields": "scope", "depth": "2", "names": "true" } }
+        }))])
+        .expect("Failed to build AppConfig");
+
+        let spans = config.observability_cfg.log_spans;
+        assert_eq!(spans.depth, Some(2));
+        assert!(spans.names);
+
+        for spans in [
+            json!({ "depth": "two" }),
+            json!({ "names": "sometimes" }),
+            json!({ "fields": 1 }),
+            json!({ "conflict": ["inner"] }),
+        ] {
+            let config = AppConfig::<Empty>::load_from([ConfigSource::Value(
+                json!({ "log": { "spans": spans } }),
+            )]);
+            assert!(config.is_err(), "{spans} is accepted");
+        }
+    }
+
+    #[test]
+    fn args_help() {
+        let help = AppConfig::<Empty>::args_help();
+
+        assert!(help.contains("--port (default: 8000)"));
+        assert!(help.contains("--log.level (default: info)"));
+        assert!(help.contains("--server.metrics.callback.timeout (default: 1s)"));
+    }
+
+    #[test]
+    fn interpolation() {
+        std::env::set_var("INTERPOLATION_PORT", "9001");
+        std::env::set_var("INTERPOLATION_SERVICE", "interpolated");
+
+        let config = AppConfig::<Empty>::load_from([ConfigSource::String(
+            r#"
+            port = "${INTERPOLATION_PORT}"
+            [service]
+            name = "${INTERPOLATION_SERVICE}-${INTERPOLATION_MISSING:-service}"
+            [component]
+            name = "$${INTERPOLATION_SERVICE}"
+            "#,
+            FileFormat::Toml,
+        )])
+        .expect("Failed to build AppConfig");
+
+        assert_eq!(config.port, 9001);
+        assert_eq!(
+            config.observability_cfg.service_name,
+            "interpolated-service"
+        );
+        assert_eq!(
+            config.observability_cfg.component_name,
+            "${INTERPOLATION_SERVICE}"
+        );
+
+        #[derive(Debug, serde::Deserialize)]
+        struct Private {
+            pin: String,
+            workers: u32,
+        }
+
+        std::env::set_var("INTERPOLATION_PIN", "0123");
+        std::env::set_var("INTERPOLATION_WORKERS", "4");
+
+        let config = AppConfig::<Private>::load_from([ConfigSource::String(
+            r#"
+            pin = "${INTERPOLATION_PIN}"
+            workers = "${INTERPOLATION_WORKERS}"
+            "#,
+            FileFormat::Toml,
+        )])
+        .expect("Failed to build AppConfig");
+
+        assert_eq!(config.private.pin, "0123");
+        assert_eq!(config.private.workers, 4);
+
+        let error = AppConfig::<Empty>::load_from([ConfigSource::String(
+            r#"
+            [service]
+            name = "${INTERPOLATION_MISSING}"
+            "#,
+            FileFormat::Toml,
+        )])
+        .expect_err("Unresolved variable must be rejected");
+
+        let error = error.to_string();
+        assert!(error.contains("INTERPOLATION_MISSING"));
+        assert!(error.contains("service.name"));
+    }
+
+    #[test]
+    fn json_schema() {
+        let schema = AppConfig::<Empty>::json_schema();
+
+        assert_eq!(
+            schema.pointer("/properties/port/default"),
+            Some(&8000.into())
+        );
+        assert_eq!(
+            schema.pointer("/properties/log/properties/level/default"),
+            Some(&"info".into())
+        );
+        assert!(schema
+            .pointer("/properties/management/properties/endpoints/properties/metrics")
+            .is_some());
+    }
+
+    /// Returns schema of key, looking into `items` of arrays, without falling back to `additionalProperties`.
+    fn described<'a>(schema: &'a Value, path: &str) -> Option<&'a Value> {
+        path.split('.').try_fold(schema, |schema, key| {
+            let schema = schema.get("items").unwrap_or(schema);
+            schema.get("properties")?.get(key)
+        })
+    }
+
+    #[test]
+    fn json_schema_describes_default_config() {
+        let schema = AppConfig::<Empty>::json_schema();
+        let default_config = include_str!("../src/resources/default_conf.toml");
+
+        let mut table = String::new();
+        let mut undescribed = Vec::new();
+
+        // commented lines are examples of optional keys and are checked too
+        for line in default_config
+            .lines()
+            .map(|line| line.trim_start_matches('#'))
+        {
+            let line = line.split(" #").next().unwrap_or_default().trim();
+
+            let key = if let Some(header) = line.strip_prefix('[') {
+                table = header.trim_matches(['[', ']']).to_owned();
+                table.clone()
+            } else if let Some((key, _)) = line.split_once(" = ") {
+                format!("{table}.{key}").trim_start_matches('.').to_owned()
+            } else {
+                continue;
+            };
+
+            if described(&schema, &key).is_none() {
+                undescribed.push(key);
+            }
+        }
+
+        assert!(
+            undescribed.is_empty(),
+            "Keys missing in schema: {undescribed:?}"
+        );
+    }
+
+    #[test]
+    fn sample_config() {
+        let sample = AppConfig::<Empty>::sample_config();
+
+        assert!(sample.contains("\n[log]\n"));
+        assert!(sample.contains("#endpoint = \"http://localhost:4317\""));
+
+        let config =
+            AppConfig::<Empty>::load_from([ConfigSource::String(&sample, FileFormat::Toml)])
+                .expect("Failed to build AppConfig from sample config");
+
+        assert_eq!(config.port, 8000);
+        assert_eq!(config.management_cfg.endpoints.metrics.as_ref(), "/metrics");
+    }
+
+    #[test]
+    fn human_units() {
+        let config = AppConfig::<Empty>::load_from([ConfigSource::String(
+            r#"
+            [log.msg]
+            length = "2KiB"
+            [server.metrics.callback]
+            timeout = "1m30s"
+            "#,
+            FileFormat::Toml,
+        )])
+        .expect("Failed to build AppConfig");
+
+        assert_eq!(config.observability_cfg.msg_length, Some(2048));
+        assert_eq!(
+            config.observability_cfg.metrics_callback_timeout,
+            Some(Duration::from_secs(90))
+        );
+
+        let config = AppConfig::<Empty>::load_from([ConfigSource::String(
+            r#"
+            [log.msg]
+            length = 100
+            [server.metrics.callback]
+            timeout = 250
+            "#,
+            FileFormat::Toml,
+        )])
+        .expect("Failed to build AppConfig");
+
+        assert_eq!(config.observability_cfg.msg_length, Some(100));
+        assert_eq!(
+            config.observability_cfg.metrics_callback_timeout,
+            Some(Duration::from_millis(250))
+        );
+
+        for invalid in [
+            "[log.msg]\nlength = \"2XiB\"",
+            "[server.metrics.callback]\ntimeout = \"1x\"",
+        ] {
+            let config =
+                AppConfig::<Empty>::load_from([ConfigSource::String(invalid, FileFormat::Toml)]);
+            assert!(config.is_err(), "{invalid} must be rejected");
+        }
+    }
+
+    #[test]
+    fn strict_mode() {
+        std::env::set_var("STRICT_MANAGMENT_ENDPOINTS_METRICS", "/metrics");
+
+        let builder = || {
+            AppConfig::<Empty>::builder()
+                .add_default()
+                .add_str(
+                    r#"
+                    [log]
+                    levle = "debug"
+                    "#,
+                    FileFormat::Toml,
+                )
+                .add_env_prefixed("STRICT")
+        };
+
+        let config = builder()
+            .build()
+            .expect("Failed to build AppConfig without strict mode");
+        assert!(config.unknown_keys.is_empty());
+
+        let config = builder()
+            .strict(StrictMode::Warn)
+            .build()
+            .expect("Failed to build AppConfig in warn mode");
+        let unknown_keys = config
+            .unknown_keys
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            unknown_keys,
+            [
+                "/log/levle: unknown key, did you mean `/log/level`? (from string)",
+                "/managment/endpoints/metrics: unknown key, did you mean `/management/endpoints/metrics`? (from environment `STRICT_*`)",
+            ]
+        );
+
+        let Err(Error::ValidationError(errors)) = builder().strict(StrictMode::Deny).build() else {
+            panic!("Unknown keys must be rejected in deny mode");
+        };
+        assert_eq!(errors.errors().len(), 2);
+
+        let config = AppConfig::<Empty>::builder()
+            .add_default()
+            .strict(StrictMode::Deny)
+            .build()
+            .expect("Default configuration must not have unknown keys");
+        assert!(config.unknown_keys.is_empty());
+    }
+
+    #[test]
+    fn in_memory_sources() {
+        let config = AppConfig::<Empty>::load_from([
+            ConfigSource::Value(json!({
+                "port": 9001,
+                "log": { "level": "debug" },
+                "management": { "endpoints": { "metrics": "/prometheus" } }
+            })),
+            ConfigSource::Map(HashMap::from([
+                ("port".to_owned(), "9002".to_owned()),
+                ("trace.level".to_owned(), "warn".to_owned()),
+            ])),
+        ])
+        .expect("Failed to build AppConfig");
+
+        assert_eq!(config.port, 9002);
+        assert_eq!(config.observability_cfg.log_level, "debug");
+        assert_eq!(config.observability_cfg.trace_level, "warn");
+        assert_eq!(
+            config.management_cfg.endpoints.metrics.as_ref(),
+            "/prometheus"
+        );
+
+        let config = AppConfig::<Empty>::builder()
+            .set_override("port", 9003)
+            .and_then(|builder| builder.set_override("log.level", "trace"))
+            .expect("Failed to set override")
+            .add_default()
+            .add_value(json!({ "port": 9004 }))
+            .with_provenance()
+            .build()
+            .expect("Failed to build AppConfig");
+
+        assert_eq!(config.port, 9003);
+        assert_eq!(config.observability_cfg.log_level, "trace");
+
+        let port = config
+            .explain()
+            .and_then(|provenance| provenance.get("port"))
+            .expect("No provenance for port");
+        assert_eq!(port.source, "override `port`");
+        assert_eq!(port.overridden, ["default configuration", "value"]);
+
+        assert!(AppConfig::<Empty>::load_from([ConfigSource::Value(json!("port"))]).is_err());
+    }
+
+    #[test]
+    fn sections() {
+        #[derive(Debug, PartialEq, serde::Deserialize)]
+        struct Pool {
+            size: usize,
+            #[serde(deserialize_with = "fregate::deserialize_duration")]
+            timeout: Duration,
+        }
+
+        let config = AppConfig::<Empty>::load_from([ConfigSource::Value(json!({
+            "db": { "pool": { "size": 10, "timeout": "5s" } }
+        }))])
+        .expect("Failed to build AppConfig");
+
+        let pool = config
+            .section::<Pool>("/db/pool")
+            .expect("Failed to read /db/pool");
+        assert_eq!(
+            pool,
+            Pool {
+                size: 10,
+                timeout: Duration::from_secs(5)
+            }
+        );
+
+        let section = format!("/{}", "db");
+        assert!(config.section::<serde_json::Value>(&section).is_ok());
+        assert_eq!(config.get::<usize>("db.pool.size").ok(), Some(10));
+        assert_eq!(
+            config.get::<String>("log.level").ok().as_deref(),
+            Some("info")
+        );
+
+        let error = config
+            .get::<usize>("db.pool.missing")
+            .expect_err("Missing key must be an error");
+        assert!(matches!(
+            error,
+            Error::ConfigSectionError { pointer, message } if pointer == "/db/pool/missing" && message == "missing key"
+        ));
+
+        let error = config
+            .get::<usize>("db.pool.timeout")
+            .expect_err("Invalid type must be an error");
+        assert!(error.to_string().contains("/db/pool/timeout"));
+    }
 }
@@ -1,5 +1,6 @@
 mod app_config_from_env {
-    use fregate::{bootstrap, AppConfig, ConfigSource};
+    use fregate::error::Error;
+    use fregate::{bootstrap, AppConfig, ConfigSource, Empty};
     use serde::Deserialize;
     use std::net::{IpAddr, Ipv6Addr};
 
@@ -72,8 +73,6 @@ mod app_config_from_env {
     async fn test_management_config_from_env() {
         std::env::set_var("MNGM_MANAGEMENT_ENDPOINTS_METRICS", "/probe/metrics");
         std::env::set_var("MNGM_MANAGEMENT_ENDPOINTS_HEALTH", "///valid");
-        std::env::set_var("MNGM_MANAGEMENT_ENDPOINTS_LIVE", "invalid");
-        std::env::set_var("MNGM_MANAGEMENT_ENDPOINTS_READY", "");
 
         let config: AppConfig =
             bootstrap([ConfigSource::EnvPrefix("MNGM")]).expect("Failed to build AppConfig");
@@ -85,4 +84,37 @@ mod app_config_from_env {
         assert_eq!(management_cfg.endpoints.live.as_ref(), "/live");
         assert_eq!(management_cfg.endpoints.ready.as_ref(), "/ready");
     }
+
+    #[test]
+    fn invalid_management_endpoints_from_env() {
+        std::env::set_var("INVALID_MANAGEMENT_ENDPOINTS_LIVE", "invalid");
+        std::env::set_var("INVALID_MANAGEMENT_ENDPOINTS_READY", "");
+
+        let error = AppConfig::<Empty>::load_from([ConfigSource::EnvPrefix("INVALID")])
+            .expect_err("Invalid endpoints must be rejected");
+
+        let Error::ValidationError(errors) = error else {
+            panic!("Unexpected error: {error}");
+        };
+
+        let pointers = errors
+            .errors()
+            .iter()
+            .map(|error| (error.pointer.as_str(), error.source.as_deref()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            pointers,
+            vec![
+                (
+                    "/management/endpoints/live",
+                    Some("environment `INVALID_*`")
+                ),
+                (
+                    "/management/endpoints/ready",
+                    Some("environment `INVALID_*`")
+                ),
+            ]
+        );
+    }
 }
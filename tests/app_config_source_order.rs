@@ -19,4 +19,46 @@ mod app_config_source_order {
 
         assert_eq!(config.port, 9999);
     }
+
+    #[test]
+    fn explain() {
+        std::env::set_var("EXPLAIN_PORT", "9999");
+
+        let config = AppConfig::<Empty>::load_from([
+            ConfigSource::File("./tests/resources/test_conf.toml"),
+            ConfigSource::EnvPrefix("EXPLAIN"),
+        ])
+        .expect("Failed to build AppConfig");
+
+        assert!(config.explain().is_none());
+
+        std::env::set_var("EXPLAIN_CONFIG_PROVENANCE", "true");
+
+        let config = AppConfig::<Empty>::load_from([
+            ConfigSource::File("./tests/resources/test_conf.toml"),
+            ConfigSource::EnvPrefix("EXPLAIN"),
+        ])
+        .expect("Failed to build AppConfig");
+
+        let provenance = config.explain().expect("Provenance is not recorded");
+        let port = provenance.get("port").expect("No provenance for port");
+
+        assert_eq!(port.source, "environment `EXPLAIN_*`");
+        assert_eq!(
+            port.overridden,
+            [
+                "default configuration",
+                "file `./tests/resources/test_conf.toml`"
+            ]
+        );
+
+        let length = provenance
+            .get("log.msg.length")
+            .expect("No provenance for log.msg.length");
+        assert_eq!(length.source, "default configuration");
+        assert!(length.overridden.is_empty());
+        assert!(provenance
+            .to_string()
+            .contains("port: environment `EXPLAIN_*` (overrides default configuration, file `./tests/resources/test_conf.toml`)"));
+    }
 }
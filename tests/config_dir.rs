@@ -0,0 +1,67 @@
+mod config_dir {
+    use fregate::{AppConfig, ConfigSource, Empty};
+
+    #[test]
+    fn lexical_merge() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path();
+        std::fs::write(
+            dir.join("10-base.toml"),
+            "port = 9000\n[log]\nlevel = \"debug\"\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("20-override.yaml"), "port: 9001\n").unwrap();
+        std::fs::write(
+            dir.join("30-service.json"),
+            r#"{"service": {"name": "conf.d"}}"#,
+        )
+        .unwrap();
+        std::fs::write(dir.join(".hidden.toml"), "port = 1").unwrap();
+        std::fs::write(dir.join("README.md"), "port = 2").unwrap();
+
+        let config = AppConfig::<Empty>::load_from([ConfigSource::Dir(dir.to_str().unwrap())])
+            .expect("Failed to build AppConfig");
+
+        assert_eq!(config.port, 9001);
+        assert_eq!(config.observability_cfg.log_level, "debug");
+        assert_eq!(config.observability_cfg.service_name, "conf.d");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn kubernetes_data_symlink() {
+        use std::os::unix::fs::symlink;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path();
+        let first = dir.join("..2024_01_01");
+        let second = dir.join("..2024_01_02");
+        std::fs::create_dir_all(&first).unwrap();
+        std::fs::create_dir_all(&second).unwrap();
+        std::fs::write(first.join("app.toml"), "port = 9100").unwrap();
+        std::fs::write(second.join("app.toml"), "port = 9200").unwrap();
+
+        symlink("..2024_01_01", dir.join("..data")).unwrap();
+        symlink("..data/app.toml", dir.join("app.toml")).unwrap();
+
+        let load = || {
+            AppConfig::<Empty>::load_from([ConfigSource::Dir(dir.to_str().unwrap())])
+                .expect("Failed to build AppConfig")
+        };
+
+        assert_eq!(load().port, 9100);
+
+        symlink("..2024_01_02", dir.join("..data_tmp")).unwrap();
+        std::fs::rename(dir.join("..data_tmp"), dir.join("..data")).unwrap();
+
+        assert_eq!(load().port, 9200);
+    }
+
+    #[test]
+    fn missing_dir() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path().join("missing");
+
+        assert!(AppConfig::<Empty>::load_from([ConfigSource::Dir(dir.to_str().unwrap())]).is_err());
+    }
+}
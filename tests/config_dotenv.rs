@@ -0,0 +1,68 @@
+mod config_dotenv {
+    use fregate::{AppConfig, ConfigSource, Empty};
+
+    #[test]
+    fn dotenv() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path();
+        let path = dir.join(".env");
+        std::fs::write(
+            &path,
+            r#"
+            # local development
+            DOTENV_PORT=9100
+            export DOTENV_LOG_LEVEL="debug"
+            OTEL_SERVICE_NAME=dotenv-service
+            UNRELATED_TRACE_LEVEL=error
+            "#,
+        )
+        .unwrap();
+        let path = path.to_str().unwrap();
+
+        let config = AppConfig::<Empty>::load_from([
+            ConfigSource::DotEnv(path),
+            ConfigSource::EnvPrefix("DOTENV"),
+        ])
+        .expect("Failed to build AppConfig");
+
+        assert_eq!(config.port, 9100);
+        assert_eq!(config.observability_cfg.log_level, "debug");
+        assert_eq!(config.observability_cfg.service_name, "dotenv-service");
+        assert_eq!(config.observability_cfg.trace_level, "info");
+        assert!(std::env::var("DOTENV_PORT").is_err());
+
+        let config = AppConfig::<Empty>::load_from([
+            ConfigSource::String("port = 9200", fregate::config::FileFormat::Toml),
+            ConfigSource::DotEnv(path),
+        ])
+        .expect("Failed to build AppConfig");
+
+        assert_eq!(config.port, 9200);
+        assert_eq!(config.observability_cfg.service_name, "dotenv-service");
+    }
+
+    #[test]
+    fn missing_dotenv() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path();
+        let path = dir.join(".env");
+
+        let config = AppConfig::<Empty>::load_from([ConfigSource::DotEnv(path.to_str().unwrap())])
+            .expect("Missing .env must not be an error");
+
+        assert_eq!(config.port, 8000);
+    }
+
+    #[test]
+    fn invalid_dotenv() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path();
+        let path = dir.join(".env");
+        std::fs::write(&path, "OTEL_SERVICE_NAME\n").unwrap();
+
+        let error = AppConfig::<Empty>::load_from([ConfigSource::DotEnv(path.to_str().unwrap())])
+            .expect_err("Invalid .env must be an error");
+
+        assert!(error.to_string().contains(".env:1"));
+    }
+}
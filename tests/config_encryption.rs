@@ -0,0 +1,74 @@
+#![cfg(feature = "encryption")]
+
+mod config_encryption {
+    use fregate::{
+        encrypt_value, generate_encryption_key, AppConfig, ConfigSource, DecryptionKey, Empty,
+        Secret,
+    };
+    use serde_json::json;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    fn key_file(key: &str) -> NamedTempFile {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(key.as_bytes()).unwrap();
+        file
+    }
+
+    #[derive(Debug, serde::Deserialize)]
+    struct Private {
+        password: Secret<String>,
+        pin: String,
+    }
+
+    #[test]
+    fn decrypt_values() {
+        let key = generate_encryption_key().unwrap();
+        let file = key_file(&key);
+
+        let config = AppConfig::<Private>::builder()
+            .add_default()
+            .add_value(json!({
+                "service": { "name": encrypt_value(&key, "encrypted-service").unwrap() },
+                "port": encrypt_value(&key, "9005").unwrap(),
+                "password": encrypt_value(&key, "encrypted-password").unwrap(),
+                "pin": encrypt_value(&key, "0123").unwrap(),
+            }))
+            .decryption_key(DecryptionKey::File(
+                file.path().to_str().unwrap().to_owned(),
+            ))
+            .build()
+            .expect("Failed to build AppConfig");
+
+        assert_eq!(config.port, 9005);
+        assert_eq!(config.observability_cfg.service_name, "encrypted-service");
+        assert_eq!(config.private.password.expose(), "encrypted-password");
+        assert_eq!(config.private.pin, "0123");
+        assert!(!format!("{config:?}").contains("encrypted-password"));
+        assert!(!format!("{config:?}").contains("0123"));
+        assert!(config.sensitive_keys.contains("pin"));
+        assert!(config.sensitive_keys.contains("service.name"));
+        assert_eq!(config.tree.pointer("/pin").unwrap(), "*****");
+    }
+
+    #[test]
+    fn missing_or_wrong_key() {
+        let key = generate_encryption_key().unwrap();
+        let encrypted = encrypt_value(&key, "secret").unwrap();
+
+        let missing = AppConfig::<Empty>::load_from([ConfigSource::Value(
+            json!({ "service": { "name": encrypted } }),
+        )]);
+        assert!(missing.is_err());
+
+        let file = key_file(&generate_encryption_key().unwrap());
+        let wrong = AppConfig::<Empty>::builder()
+            .add_default()
+            .add_value(json!({ "service": { "name": encrypted } }))
+            .decryption_key(DecryptionKey::File(
+                file.path().to_str().unwrap().to_owned(),
+            ))
+            .build();
+        assert!(wrong.is_err());
+    }
+}
@@ -0,0 +1,59 @@
+mod config_profiles {
+    use fregate::{AppConfig, ConfigSource, Empty};
+
+    #[test]
+    fn profiles() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path();
+        std::fs::write(dir.join("app.toml"), "port = 8001\nprofiles = \"dev\"").unwrap();
+        std::fs::write(
+            dir.join("app.dev.toml"),
+            "port = 8002\n[log]\nlevel = \"debug\"",
+        )
+        .unwrap();
+        std::fs::write(dir.join("app.prod.yaml"), "port: 8003").unwrap();
+
+        let path = dir.join("app");
+        let path = path.to_str().unwrap();
+
+        let config = AppConfig::<Empty>::load_from([ConfigSource::Profiled(path)])
+            .expect("Failed to build AppConfig");
+
+        assert_eq!(config.profiles, vec!["dev".to_owned()]);
+        assert_eq!(config.port, 8002);
+        assert_eq!(config.observability_cfg.log_level, "debug");
+
+        std::env::set_var("APP_PROFILES", "dev, prod,missing");
+
+        let config = AppConfig::<Empty>::load_from([ConfigSource::Profiled(path)])
+            .expect("Failed to build AppConfig");
+
+        assert_eq!(config.profiles, vec!["dev", "prod", "missing"]);
+        assert_eq!(config.port, 8003);
+        assert_eq!(config.observability_cfg.log_level, "debug");
+
+        std::env::remove_var("APP_PROFILES");
+
+        let config = AppConfig::<Empty>::builder()
+            .add_default()
+            .add_profiled(path)
+            .with_provenance()
+            .build()
+            .expect("Failed to build AppConfig");
+
+        let profiles = config.explain().unwrap().get("profiles").unwrap();
+        assert_eq!(profiles.source, "override `profiles`");
+        assert_eq!(profiles.overridden, [format!("file `{path}`")]);
+
+        std::fs::write(dir.join("broken.toml"), "port = ").unwrap();
+        let broken = dir.join("broken");
+
+        let config = AppConfig::<Empty>::builder()
+            .add_default()
+            .add_file(broken.to_str().unwrap())
+            .add_profiled(path)
+            .build();
+
+        assert!(config.is_err());
+    }
+}
@@ -0,0 +1,95 @@
+mod config_reload {
+    use fregate::{AppConfig, ConfigSource, Validate, ValidationErrors};
+    use serde::Deserialize;
+    use std::fs::File;
+    use std::io::Write;
+    use std::time::{Duration, SystemTime};
+
+    #[derive(Debug, Clone, Deserialize)]
+    struct Private {
+        number: u32,
+    }
+
+    impl Validate for Private {
+        fn validate(&self, errors: &mut ValidationErrors) {
+            if self.number == 0 {
+                errors.add("/number", "must be greater than 0");
+            }
+        }
+    }
+
+    /// Writes file with explicit modification time, so change is seen regardless of file system time granularity.
+    fn write(path: &str, contents: &str, modified: u64) {
+        let mut file = File::create(path).unwrap();
+        file.write_all(contents.as_bytes()).unwrap();
+        file.set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(modified))
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn reload_on_file_change() {
+        let file = tempfile::Builder::new().suffix(".toml").tempfile().unwrap();
+        let path: &'static str =
+            Box::leak(file.path().to_string_lossy().into_owned().into_boxed_str());
+        write(path, "number = 1\nport = 8001", 1);
+
+        let sources = vec![ConfigSource::File(path)];
+        let config = AppConfig::<Private>::load_from(sources.clone()).unwrap();
+        let mut handle = config.watch(sources, Duration::from_millis(20));
+
+        assert_eq!(handle.get().private.number, 1);
+
+        write(path, "number = 2\nport = 8002", 2);
+
+        let config = tokio::time::timeout(Duration::from_secs(5), handle.changed())
+            .await
+            .expect("Config must be reloaded")
+            .unwrap();
+        assert_eq!(config.private.number, 2);
+        assert_eq!(config.port, 8002);
+
+        write(path, "number = \"invalid\"", 3);
+
+        let changed = tokio::time::timeout(Duration::from_millis(300), handle.changed()).await;
+        assert!(changed.is_err(), "Invalid config must be rejected");
+        assert_eq!(handle.get().private.number, 2);
+
+        write(
+            path,
+            "number = 3\n[management.endpoints]\nhealth = \"health\"",
+            4,
+        );
+
+        let changed = tokio::time::timeout(Duration::from_millis(300), handle.changed()).await;
+        assert!(
+            changed.is_err(),
+            "Config failing validation must be rejected"
+        );
+        assert_eq!(handle.get().private.number, 2);
+    }
+
+    #[tokio::test]
+    async fn reload_validated() {
+        let file = tempfile::Builder::new().suffix(".toml").tempfile().unwrap();
+        let path: &'static str =
+            Box::leak(file.path().to_string_lossy().into_owned().into_boxed_str());
+        write(path, "number = 1", 1);
+
+        let sources = vec![ConfigSource::File(path)];
+        let config = AppConfig::<Private>::load_validated_from(sources.clone()).unwrap();
+        let mut handle = config.watch_validated(sources, Duration::from_millis(20));
+
+        write(path, "number = 0", 2);
+
+        let changed = tokio::time::timeout(Duration::from_millis(300), handle.changed()).await;
+        assert!(changed.is_err(), "Invalid private config must be rejected");
+
+        write(path, "number = 3", 3);
+
+        let config = tokio::time::timeout(Duration::from_secs(5), handle.changed())
+            .await
+            .expect("Config must be reloaded")
+            .unwrap();
+        assert_eq!(config.private.number, 3);
+    }
+}
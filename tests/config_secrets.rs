@@ -0,0 +1,98 @@
+mod config_secrets {
+    use fregate::{AppConfig, ConfigSource, Secret};
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize)]
+    struct Private {
+        db: Db,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Db {
+        user: String,
+        password: Secret<String>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Plain {
+        db: PlainDb,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct PlainDb {
+        user: String,
+        password: String,
+    }
+
+    #[test]
+    fn secrets_dir() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path();
+        std::fs::create_dir_all(dir.join("db")).unwrap();
+        std::fs::create_dir_all(dir.join("..data")).unwrap();
+        std::fs::write(dir.join("db/user"), "admin\n").unwrap();
+        std::fs::write(dir.join("db/password"), "dir-password\n").unwrap();
+        std::fs::write(dir.join("..data/ignored"), "ignored").unwrap();
+
+        let config =
+            AppConfig::<Private>::load_from([ConfigSource::SecretsDir(dir.to_str().unwrap())])
+                .expect("Failed to build AppConfig");
+
+        assert_eq!(config.private.db.user, "admin");
+        assert_eq!(config.private.db.password.expose(), "dir-password");
+        assert!(!format!("{config:?}").contains("dir-password"));
+        assert!(!format!("{config:#?}").contains("dir-password"));
+    }
+
+    #[test]
+    fn secrets_dir_redacted() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path();
+        std::fs::create_dir_all(dir.join("db")).unwrap();
+        std::fs::write(dir.join("db/user"), "admin").unwrap();
+        std::fs::write(dir.join("db/password"), "plain \"password\"").unwrap();
+
+        let config =
+            AppConfig::<Plain>::load_from([ConfigSource::SecretsDir(dir.to_str().unwrap())])
+                .expect("Failed to build AppConfig");
+
+        assert_eq!(config.private.db.password, "plain \"password\"");
+        assert!(config.sensitive_keys.contains("db.password"));
+        assert_eq!(config.tree.pointer("/db/password").unwrap(), "*****");
+        assert_eq!(config.get::<String>("db.password").unwrap(), "*****");
+        assert!(!format!("{config:?}").contains("password\\\""));
+        assert!(!format!("{config:#?}").contains("password\\\""));
+    }
+
+    #[test]
+    fn env_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path();
+        std::fs::write(dir.join("password"), "env-password").unwrap();
+
+        std::env::set_var("SECRET_DB_USER", "admin");
+        std::env::set_var("SECRET_DB_PASSWORD_FILE", dir.join("password"));
+
+        let config = AppConfig::<Plain>::load_from([ConfigSource::EnvPrefix("SECRET")])
+            .expect("Failed to build AppConfig");
+
+        assert_eq!(config.private.db.user, "admin");
+        assert_eq!(config.private.db.password, "env-password");
+        assert!(config.tree.pointer("/db/password/file").is_none());
+        assert_eq!(config.tree.pointer("/db/password").unwrap(), "*****");
+        assert_eq!(
+            config.sensitive_keys.keys().collect::<Vec<_>>(),
+            ["db.password"]
+        );
+        assert!(!format!("{config:?}").contains("env-password"));
+    }
+
+    #[test]
+    fn env_file_missing() {
+        std::env::set_var("MISSING_DB_USER", "admin");
+        std::env::set_var("MISSING_DB_PASSWORD_FILE", "/non/existing/secret");
+
+        let config = AppConfig::<Plain>::load_from([ConfigSource::EnvPrefix("MISSING")]);
+        assert!(config.is_err());
+    }
+}
@@ -0,0 +1,62 @@
+mod log_file_output {
+    use fregate::serde_json::json;
+    use fregate::{
+        bootstrap, AppConfig, ConfigSource, Empty, LogOutput, LogRotation, SyslogConfig,
+    };
+
+    #[tokio::test]
+    async fn writes_to_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.log");
+
+        let mut config: AppConfig = bootstrap([ConfigSource::Value(json!({
+            "log": {
+                "output": "file",
+                "file": { "path": path.to_str().unwrap(), "rotation": "1MiB", "retain": 3 }
+            }
+        }))])
+        .unwrap();
+
+        let LogOutput::File(file) = &config.observability_cfg.log_output else {
+            panic!("expected file output");
+        };
+        assert_eq!(file.rotation, LogRotation::Size(1_048_576));
+        assert_eq!(file.retain, Some(3));
+        assert!(!file.compress);
+
+        tracing::info!("written to file");
+        drop(config.worker_guard.take());
+        drop(config.log_guard.take());
+
+        let logs = std::fs::read_to_string(&path).unwrap();
+        assert!(logs.contains("\"msg\":\"written to file\""));
+    }
+
+    #[test]
+    fn invalid_output() {
+        let load =
+            |log| AppConfig::<Empty>::load_from([ConfigSource::Value(json!({ "log": log }))]);
+
+        assert_eq!(
+            load(json!({})).unwrap().observability_cfg.log_output,
+            LogOutput::Stdout
+        );
+        assert!(load(json!({ "output": "file" })).is_err());
+        assert!(load(json!({ "output": "journald" })).is_err());
+        assert_eq!(
+            load(json!({ "output": "syslog", "syslog": { "facility": "local3" } }))
+                .unwrap()
+                .observability_cfg
+                .log_output,
+            LogOutput::Syslog(SyslogConfig {
+                path: "/dev/log".into(),
+                facility: 19,
+            })
+        );
+        assert!(load(json!({ "output": "syslog", "syslog": { "facility": "local9" } })).is_err());
+        assert!(load(
+            json!({ "output": "file", "file": { "path": "app.log", "rotation": "weekly" } })
+        )
+        .is_err());
+    }
+}
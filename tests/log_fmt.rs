@@ -12,6 +12,9 @@ mod log_fmt_test {
 
     use fregate::observability::EventFormatter;
     use fregate::observability::TracingFields;
+    use fregate::observability::{LogField, LogSchema};
+    use fregate::observability::{LogSpans, SpanConflict, SpanFieldsLayer, SpanFieldsMode};
+    use fregate::LogFormat;
     #[cfg(tracing_unstable)]
     use valuable::Valuable;
 
@@ -439,4 +442,281 @@ mod log_fmt_test {
 
         compare(expected, content.as_str());
     }
+
+    fn text_subscriber(
+        formatter: EventFormatter,
+        writer: MockMakeWriter,
+        ansi: bool,
+    ) -> impl tracing::Subscriber {
+        use tracing_subscriber::layer::SubscriberExt;
+
+        tracing_subscriber::registry().with(
+            tracing_subscriber::fmt::layer()
+                .event_format(formatter)
+                .with_writer(writer)
+                .with_ansi(ansi),
+        )
+    }
+
+    /// Drops leading timestamp of text formats.
+    fn without_timestamp(content: &str) -> &str {
+        content.split_once(' ').unwrap().1
+    }
+
+    #[test]
+    fn compact_format() {
+        let mock_writer = MockMakeWriter::new();
+        let mut formatter =
+            EventFormatter::new_with_limit(Some(10)).with_format(LogFormat::Compact);
+        formatter.add_field_to_events("field_1", 999).unwrap();
+
+        with_default(
+            text_subscriber(formatter, mock_writer.clone(), false),
+            || {
+                tracing::warn!(check = 100, text = "with space", "12345678901");
+            },
+        );
+
+        let content = mock_writer.get_content();
+        let expected = " WARN log_fmt::log_fmt_test: \"1234567890 ...\" field_1=999 check=100 text=\"with space\"\n";
+
+        assert_eq!(without_timestamp(&content), expected);
+    }
+
+    #[test]
+    #[cfg(tracing_unstable)]
+    fn compact_format_marker() {
+        let mock_writer = MockMakeWriter::new();
+        let formatter = EventFormatter::new().with_format(LogFormat::Compact);
+
+        with_default(
+            text_subscriber(formatter, mock_writer.clone(), false),
+            || {
+                let mut marker = TracingFields::with_capacity(1);
+                marker.insert_ref("flattened", &"value");
+                tracing::info!(marker = marker.as_value(), "test");
+            },
+        );
+
+        let content = mock_writer.get_content();
+        assert_eq!(
+            without_timestamp(&content),
+            " INFO log_fmt::log_fmt_test: test flattened=value\n"
+        );
+    }
+
+    #[test]
+    fn pretty_format() {
+        let mock_writer = MockMakeWriter::new();
+        let mut formatter = EventFormatter::new().with_format(LogFormat::Pretty);
+        formatter.add_field_to_events("field_1", "value_1").unwrap();
+
+        with_default(
+            text_subscriber(formatter, mock_writer.clone(), false),
+            || {
+                tracing::info!(check = 100, "test");
+            },
+        );
+
+        let content = mock_writer.get_content();
+        let expected = " INFO log_fmt::log_fmt_test: test\n    field_1: value_1\n    check: 100\n";
+
+        assert_eq!(without_timestamp(&content), expected);
+    }
+
+    #[test]
+    fn syslog_priority() {
+        let mock_writer = MockMakeWriter::new();
+        let subscriber = subscriber(EventFormatter::new().with_syslog_facility(16))
+            .with_writer(mock_writer.clone())
+            .finish();
+
+        with_default(subscriber, || {
+            tracing::warn!("test");
+        });
+
+        let content = mock_writer.get_content();
+        let line = content
+            .strip_prefix("<132>")
+            .expect("Line must start with local0.warning priority");
+        let expected =
+            "{\"LogLevel\":\"WARN\",\"msg\":\"test\",\"target\":\"log_fmt::log_fmt_test\"}\n";
+
+        compare(expected, line);
+    }
+
+    #[test]
+    fn colored_format() {
+        let mock_writer = MockMakeWriter::new();
+        let formatter = EventFormatter::new().with_format(LogFormat::Compact);
+
+        with_default(
+            text_subscriber(formatter, mock_writer.clone(), true),
+            || {
+                tracing::error!("test");
+            },
+        );
+
+        let content = mock_writer.get_content();
+        assert!(content.contains("\x1b[31mERROR\x1b[0m"));
+    }
+
+    #[test]
+    fn gcp_schema() {
+        let mock_writer = MockMakeWriter::new();
+        let formatter = EventFormatter::new().with_schema(LogSchema::gcp());
+
+        let subscriber = subscriber(formatter)
+            .with_writer(mock_writer.clone())
+            .finish();
+
+        with_default(subscriber, || {
+            tracing::warn!(check = 100, "test");
+        });
+
+        let content = mock_writer.get_content();
+        let mut actual = serde_json::from_str::<HashMap<&str, Value>>(&content).unwrap();
+
+        assert!(actual.remove("time").unwrap().is_string());
+        let expected = serde_json::json!({
+            "severity": "WARNING",
+            "target": "log_fmt::log_fmt_test",
+            "message": "test",
+            "check": 100
+        });
+        assert_eq!(serde_json::to_value(actual).unwrap(), expected);
+    }
+
+    #[test]
+    fn remapped_schema() {
+        let mock_writer = MockMakeWriter::new();
+        let schema = LogSchema::default()
+            .rename(LogField::Level, "level")
+            .rename(LogField::Msg, "message")
+            .omit(LogField::Time)
+            .omit(LogField::Target)
+            .map_level(tracing::Level::INFO, "information");
+
+        let subscriber = subscriber(EventFormatter::new().with_schema(schema))
+            .with_writer(mock_writer.clone())
+            .finish();
+
+        with_default(subscriber, || {
+            tracing::info!(LogLevel = "event field", "test");
+        });
+
+        let content = mock_writer.get_content();
+        let mut actual = serde_json::from_str::<HashMap<&str, Value>>(&content).unwrap();
+
+        assert!(actual.remove("timestamp").is_some());
+        let expected = serde_json::json!({
+            "level": "information",
+            "message": "test",
+            "LogLevel": "event field"
+        });
+        assert_eq!(serde_json::to_value(actual).unwrap(), expected);
+    }
+
+    #[test]
+    fn schema_reserved_fields() {
+        let mut formatter = EventFormatter::new().with_schema(LogSchema::ecs());
+
+        assert!(formatter.add_field_to_events("log.level", "INFO").is_err());
+        assert!(formatter.add_field_to_events("message", "Hello").is_err());
+        assert!(formatter.add_field_to_events("msg", "Hello").is_ok());
+        assert!(formatter.add_field_to_events("LogLevel", "INFO").is_ok());
+    }
+
+    fn spans_content(spans: LogSpans) -> String {
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let mock_writer = MockMakeWriter::new();
+        let subscriber = subscriber(EventFormatter::new().with_spans(spans))
+            .with_writer(mock_writer.clone())
+            .finish()
+            .with(SpanFieldsLayer);
+
+        with_default(subscriber, || {
+            let outer = tracing::info_span!("outer", user = 1, route = "/outer");
+            let _outer = outer.enter();
+            let inner = tracing::info_span!("inner", user = 2, late = tracing::field::Empty);
+            let _inner = inner.enter();
+            inner.record("late", "recorded");
+
+            tracing::info!(route = "event", "test");
+        });
+
+        mock_writer.get_content()
+    }
+
+    #[test]
+    fn no_span_fields() {
+        let content = spans_content(LogSpans::default());
+        let expected = "{\"LogLevel\":\"INFO\",\"msg\":\"test\",\"target\":\"log_fmt::log_fmt_test\",\"route\":\"event\"}\n";
+
+        compare(expected, content.as_str());
+    }
+
+    #[test]
+    fn current_span_fields() {
+        let content = spans_content(LogSpans {
+            fields: SpanFieldsMode::Current,
+            ..Default::default()
+        });
+        let expected = "{\"LogLevel\":\"INFO\",\"msg\":\"test\",\"target\":\"log_fmt::log_fmt_test\",\"route\":\"event\",\"user\":2,\"late\":\"recorded\"}\n";
+
+        compare(expected, content.as_str());
+    }
+
+    #[test]
+    fn scope_span_fields() {
+        let inner = spans_content(LogSpans {
+            fields: SpanFieldsMode::Scope,
+            ..Default::default()
+        });
+        let expected = "{\"LogLevel\":\"INFO\",\"msg\":\"test\",\"target\":\"log_fmt::log_fmt_test\",\"route\":\"event\",\"user\":2,\"late\":\"recorded\"}\n";
+        compare(expected, inner.as_str());
+
+        let outer = spans_content(LogSpans {
+            fields: SpanFieldsMode::Scope,
+            conflict: SpanConflict::Outer,
+            ..Default::default()
+        });
+        let expected = "{\"LogLevel\":\"INFO\",\"msg\":\"test\",\"target\":\"log_fmt::log_fmt_test\",\"route\":\"event\",\"user\":1,\"late\":\"recorded\"}\n";
+        compare(expected, outer.as_str());
+
+        let limited = spans_content(LogSpans {
+            fields: SpanFieldsMode::Scope,
+            depth: Some(1),
+            conflict: SpanConflict::Outer,
+            names: false,
+        });
+        let expected = "{\"LogLevel\":\"INFO\",\"msg\":\"test\",\"target\":\"log_fmt::log_fmt_test\",\"route\":\"event\",\"user\":2,\"late\":\"recorded\"}\n";
+        compare(expected, limited.as_str());
+    }
+
+    #[test]
+    fn span_names() {
+        let content = spans_content(LogSpans {
+            names: true,
+            ..Default::default()
+        });
+        let expected = "{\"LogLevel\":\"INFO\",\"msg\":\"test\",\"target\":\"log_fmt::log_fmt_test\",\"route\":\"event\",\"spans\":[\"outer\",\"inner\"]}\n";
+
+        compare(expected, content.as_str());
+    }
+
+    #[test]
+    fn spans_reserved_field() {
+        assert!(EventFormatter::new()
+            .add_field_to_events("spans", "value")
+            .is_ok());
+        assert!(EventFormatter::new()
+            .with_spans(LogSpans {
+                names: true,
+                ..Default::default()
+            })
+            .add_field_to_events("spans", "value")
+            .is_err());
+    }
 }
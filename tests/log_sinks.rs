@@ -0,0 +1,68 @@
+mod log_sinks {
+    use fregate::observability::LOG_SINK_HANDLES;
+    use fregate::serde_json::json;
+    use fregate::{bootstrap, AppConfig, ConfigSource, Empty};
+    use std::str::FromStr;
+    use tracing_subscriber::EnvFilter;
+
+    #[tokio::test]
+    async fn per_sink_levels() {
+        let dir = tempfile::tempdir().unwrap();
+        let debug_path = dir.path().join("debug.log");
+        let warn_path = dir.path().join("warn.log");
+
+        let mut config: AppConfig = bootstrap([ConfigSource::Value(json!({
+            "log": {
+                "level": "info",
+                "sinks": [
+                    { "name": "debug", "level": "debug", "output": "file", "file": { "path": debug_path.to_str().unwrap() } },
+                    { "name": "warn", "level": "warn", "output": "file", "file": { "path": warn_path.to_str().unwrap() } }
+                ]
+            }
+        }))])
+        .unwrap();
+
+        assert_eq!(config.observability_cfg.log_sinks.len(), 2);
+
+        tracing::debug!("debug message");
+        tracing::warn!("warn message");
+
+        let handles = LOG_SINK_HANDLES.get().unwrap();
+        handles
+            .get("warn")
+            .unwrap()
+            .reload(EnvFilter::from_str("debug").unwrap())
+            .unwrap();
+        tracing::debug!("after reload");
+
+        drop(config.worker_guard.take());
+        drop(config.log_guard.take());
+
+        let debug = std::fs::read_to_string(&debug_path).unwrap();
+        assert!(debug.contains("debug message"));
+        assert!(debug.contains("warn message"));
+
+        let warn = std::fs::read_to_string(&warn_path).unwrap();
+        assert!(!warn.contains("debug message"));
+        assert!(warn.contains("warn message"));
+        assert!(warn.contains("after reload"));
+    }
+
+    #[test]
+    fn invalid_sinks() {
+        let load = |sinks| {
+            AppConfig::<Empty>::load_from([ConfigSource::Value(
+                json!({ "log": { "sinks": sinks } }),
+            )])
+        };
+
+        assert!(load(json!([{ "name": "stderr", "level": "debug", "output": "stderr" }])).is_ok());
+        assert!(load(json!([{ "level": "debug" }])).is_err());
+        assert!(load(json!([{ "name": "bad", "level": "crate=nope" }])).is_err());
+        assert!(load(json!([
+            { "name": "same", "level": "info" },
+            { "name": "same", "level": "debug" }
+        ]))
+        .is_err());
+    }
+}